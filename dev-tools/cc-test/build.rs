@@ -42,6 +42,22 @@ fn main() {
         .compile("foo");
 
     let compiler = build.get_compiler();
+    let version = compiler.version().unwrap();
+    build
+        .require_compiler_version(compiler.family(), version)
+        .unwrap();
+    build
+        .require_compiler_version(compiler.family(), (version.major() + 1, 0))
+        .unwrap_err();
+    let macros = compiler.predefined_macros().unwrap();
+    let include_dirs = compiler.system_include_dirs().unwrap();
+    if !compiler.is_like_msvc() {
+        assert!(macros
+            .iter()
+            .any(|(name, value)| name == "__STDC__" && value == "1"));
+        assert!(include_dirs.iter().all(|dir| dir.is_dir()));
+        assert!(!include_dirs.is_empty());
+    }
 
     cc::Build::new()
         .file("src/bar1.c")
@@ -49,6 +65,35 @@ fn main() {
         .include("src/include")
         .compile("bar");
 
+    let probe = cc::Build::new();
+    assert!(probe.probe_cfg("cc_test_has_stddef_h", "#include <stddef.h>"));
+    assert!(!probe.probe_cfg(
+        "cc_test_has_missing_h",
+        "#include <cc_test_missing_header.h>"
+    ));
+
+    let mut build = cc::Build::new();
+    let mut checks = cc::checks::Checks::new(&build);
+    assert!(checks.check_header("stddef.h").unwrap());
+    assert!(!checks.check_header("cc_test_missing_header.h").unwrap());
+    assert_eq!(checks.check_type_size("char").unwrap(), Some(1));
+    assert_eq!(
+        checks.check_type_size("struct cc_test_missing").unwrap(),
+        None
+    );
+    checks.check_type_size("int").unwrap();
+    assert!(checks.check_function("malloc").unwrap());
+    assert!(!checks.check_function("cc_test_missing_function").unwrap());
+    checks
+        .check_struct_member("struct tm", "tm_sec", "time.h")
+        .unwrap();
+    checks
+        .check_symbol_in_header("offsetof", "stddef.h")
+        .unwrap();
+    checks.define("CC_TEST_CHECKS", "1");
+    checks.write_config_h(&mut build, "config.h").unwrap();
+    build.file("src/checks.c").compile("checks");
+
     let target = std::env::var("TARGET").unwrap();
     let arch = match target.split('-').next().unwrap() {
         "arm64_32" => "aarch64",
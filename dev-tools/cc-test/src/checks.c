@@ -0,0 +1,13 @@
+#include "config.h"
+
+#if !defined(CC_TEST_CHECKS) || !defined(HAVE_STDDEF_H) || defined(HAVE_CC_TEST_MISSING_HEADER_H)
+#error "unexpected config.h contents"
+#endif
+
+#if !defined(HAVE_STRUCT_TM_TM_SEC) || !defined(HAVE_OFFSETOF)
+#error "unexpected config.h contents"
+#endif
+
+int checks(void) {
+  return SIZEOF_INT == sizeof(int) && SIZEOF_CHAR == 1;
+}
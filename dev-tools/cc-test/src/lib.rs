@@ -8,6 +8,8 @@ extern "C" {
 
     pub fn baz() -> i32;
 
+    pub fn checks() -> i32;
+
     #[cfg(windows)]
     pub fn windows();
 
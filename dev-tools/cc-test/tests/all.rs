@@ -34,6 +34,13 @@ fn baz_here() {
     }
 }
 
+#[test]
+fn checks_here() {
+    unsafe {
+        assert_eq!(checks(), 1);
+    }
+}
+
 #[test]
 #[cfg(windows)]
 fn windows_here() {
@@ -67,3 +74,11 @@ fn cuda_here() {
         cuda_kernel();
     }
 }
+
+#[test]
+fn probe_cfg() {
+    #[cfg(not(cc_test_has_stddef_h))]
+    panic!("`cc_test_has_stddef_h` should be set");
+    #[cfg(cc_test_has_missing_h)]
+    panic!("`cc_test_has_missing_h` should not be set");
+}
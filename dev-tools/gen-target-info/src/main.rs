@@ -3,14 +3,55 @@ use std::{fs::File, io::BufRead};
 
 use gen_target_info::{
     get_target_spec_from_msrv, get_target_specs_from_json, get_targets_msrv, RustcTargetSpecs,
+    TargetSpec,
 };
 
 const PRELUDE: &str = r#"//! This file is generated code. Please edit the generator in
 //! dev-tools/gen-target-info if you need to make changes, or see
 //! src/target/llvm.rs if you need to configure a specific LLVM triple.
 
+use super::TargetDefaults;
+
 "#;
 
+/// The Elbrus 2000 targets of MCST's fork of `rustc`, which upstream `rustc`
+/// doesn't know. The fork passes the CPU to LCC through the target name.
+const E2K_TARGETS: &[&str] = &[
+    "e2k-unknown-linux-gnu",
+    "e2k12c-unknown-linux-gnu",
+    "e2k16c-unknown-linux-gnu",
+    "e2k1cplus-unknown-linux-gnu",
+    "e2k2c3-unknown-linux-gnu",
+    "e2k48c-unknown-linux-gnu",
+    "e2k4c-unknown-linux-gnu",
+    "e2k8c-unknown-linux-gnu",
+    "e2k8c2-unknown-linux-gnu",
+    "e2k8v7-unknown-linux-gnu",
+    "e2kv3-unknown-linux-gnu",
+    "e2kv4-unknown-linux-gnu",
+    "e2kv5-unknown-linux-gnu",
+    "e2kv6-unknown-linux-gnu",
+    "e2kv7-unknown-linux-gnu",
+];
+
+fn e2k_target_spec() -> TargetSpec {
+    TargetSpec {
+        arch: "e2k".to_string(),
+        llvm_target: "e2k-unknown-linux-gnu".to_string(),
+        link_env_remove: None,
+        link_env: None,
+        os: Some("linux".to_string()),
+        vendor: Some("unknown".to_string()),
+        env: Some("gnu".to_string()),
+        abi: None,
+        pre_link_args: None,
+        cpu: None,
+        features: None,
+        max_atomic_width: Some(64),
+        target_pointer_width: 64,
+    }
+}
+
 fn generate_target_mapping(f: &mut File, target_specs: &RustcTargetSpecs) -> std::io::Result<()> {
     writeln!(f, "#[rustfmt::skip]")?;
     writeln!(f, "pub(crate) const LLVM_TARGETS: &[(&str, &str)] = &[")?;
@@ -24,6 +65,30 @@ fn generate_target_mapping(f: &mut File, target_specs: &RustcTargetSpecs) -> std
     Ok(())
 }
 
+fn generate_target_defaults(f: &mut File, target_specs: &RustcTargetSpecs) -> std::io::Result<()> {
+    writeln!(f, "#[rustfmt::skip]")?;
+    writeln!(
+        f,
+        "pub(crate) const TARGET_DEFAULTS: &[(&str, TargetDefaults<'static>)] = &["
+    )?;
+
+    for (target_name, spec) in &target_specs.0 {
+        writeln!(
+            f,
+            "    ({target_name:?}, TargetDefaults {{ cpu: {:?}, features: {:?}, max_atomic_width: {:?}, pointer_width: {} }}),",
+            spec.cpu.as_deref().unwrap_or_default(),
+            spec.features.as_deref().unwrap_or_default(),
+            spec.max_atomic_width,
+            spec.target_pointer_width,
+        )?;
+    }
+
+    writeln!(f, "];")?;
+
+    Ok(())
+}
+
+#[allow(clippy::disallowed_methods)]
 fn main() {
     // Primarily use information from nightly.
     let mut target_specs = get_target_specs_from_json(std::env::var("RUSTC").ok());
@@ -44,6 +109,13 @@ fn main() {
         }
     }
 
+    for target_name in E2K_TARGETS {
+        target_specs
+            .0
+            .entry(target_name.to_string())
+            .or_insert_with(e2k_target_spec);
+    }
+
     // Open file to write to
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
 
@@ -54,6 +126,8 @@ fn main() {
 
     // Start generating
     generate_target_mapping(&mut f, &target_specs).unwrap();
+    writeln!(f).unwrap();
+    generate_target_defaults(&mut f, &target_specs).unwrap();
 
     // Flush the data onto disk
     f.flush().unwrap();
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use std::collections::BTreeMap;
 
 #[derive(Debug, Deserialize)]
@@ -24,6 +24,29 @@ pub struct TargetSpec {
     pub env: Option<String>,
     pub abi: Option<String>,
     pub pre_link_args: Option<PreLinkArgs>,
+    /// The default CPU, e.g. `pentium4`
+    pub cpu: Option<String>,
+    /// The default target features, e.g. `+v7,+vfp3d16,+thumb2,-neon`
+    pub features: Option<String>,
+    pub max_atomic_width: Option<u32>,
+    #[serde(deserialize_with = "deserialize_pointer_width")]
+    pub target_pointer_width: u32,
+}
+
+/// `target-pointer-width` is a string in older versions of `rustc`, and a
+/// number in newer ones.
+fn deserialize_pointer_width<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PointerWidth {
+        Number(u32),
+        String(String),
+    }
+
+    match PointerWidth::deserialize(deserializer)? {
+        PointerWidth::Number(width) => Ok(width),
+        PointerWidth::String(width) => width.parse().map_err(serde::de::Error::custom),
+    }
 }
 
 #[derive(Debug, Deserialize)]
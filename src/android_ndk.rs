@@ -0,0 +1,184 @@
+//! Discovery of the Android NDK, so that Android targets can be built
+//! without pointing `CC` at one of its `<triple><api>-clang` wrappers.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::apple_sdk::compare_versions;
+use crate::json;
+
+/// An NDK installation, e.g. `$ANDROID_HOME/ndk/26.1.10909125`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AndroidNdk {
+    pub(crate) path: PathBuf,
+    /// The `Pkg.Revision` of the NDK, e.g. `26.1.10909125`.
+    pub(crate) revision: Box<str>,
+    /// The lowest API level that the NDK supports.
+    pub(crate) min_api_level: u32,
+}
+
+impl AndroidNdk {
+    /// Read the NDK at `path`, if it is one, i.e. it has a `source.properties`.
+    pub(crate) fn read(path: &Path) -> Option<AndroidNdk> {
+        let properties = fs::read_to_string(path.join("source.properties")).ok()?;
+        let revision: Box<str> = properties.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "Pkg.Revision").then(|| Box::from(value.trim()))
+        })?;
+
+        // `meta/platforms.json` lists the supported API levels, otherwise
+        // they are known from the release notes of each revision.
+        let min_api_level = fs::read_to_string(path.join("meta/platforms.json"))
+            .ok()
+            .and_then(
+                |platforms| match json::parse(&platforms).ok()?.get("min")? {
+                    json::Value::Number(min) => min.parse().ok(),
+                    _ => None,
+                },
+            )
+            .unwrap_or_else(|| {
+                match revision
+                    .split('.')
+                    .next()
+                    .and_then(|major| major.parse::<u32>().ok())
+                {
+                    Some(26..) => 21,
+                    Some(24..=25) => 19,
+                    Some(16..=23) => 16,
+                    _ => 14,
+                }
+            });
+
+        Some(AndroidNdk {
+            path: path.to_path_buf(),
+            revision,
+            min_api_level,
+        })
+    }
+
+    /// Find the newest NDK in `dir`, e.g. `$ANDROID_HOME/ndk`, which has one
+    /// directory per installed revision.
+    pub(crate) fn find_newest(dir: &Path) -> Option<AndroidNdk> {
+        let mut ndks: Vec<_> = fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| AndroidNdk::read(&entry.ok()?.path()))
+            .collect();
+        // `read_dir` is in no particular order.
+        ndks.sort_by(|a, b| a.path.cmp(&b.path));
+        ndks.into_iter()
+            .rev()
+            .max_by(|a, b| compare_versions(&a.revision, &b.revision))
+    }
+
+    /// The directory of the prebuilt LLVM toolchain for `host`, which has
+    /// `clang`, `clang++` and `llvm-ar`.
+    pub(crate) fn bin_dir(&self, host: &str) -> Option<PathBuf> {
+        // The macOS toolchain is universal, and is named after x86_64 anyway.
+        let tag = if host.contains("-windows") {
+            "windows-x86_64"
+        } else if host.contains("-apple-darwin") {
+            "darwin-x86_64"
+        } else if host.contains("-linux") {
+            "linux-x86_64"
+        } else {
+            return None;
+        };
+        let bin = self
+            .path
+            .join("toolchains/llvm/prebuilt")
+            .join(tag)
+            .join("bin");
+        bin.is_dir().then_some(bin)
+    }
+}
+
+/// The lowest API level that supports the architecture at all.
+pub(crate) fn arch_min_api_level(arch: &str) -> u32 {
+    match arch {
+        "aarch64" | "x86_64" => 21,
+        "riscv64" => 35,
+        _ => 0,
+    }
+}
+
+/// The triple of the NDK's Clang for a Rust target, including the API level,
+/// e.g. `armv7a-linux-androideabi21` for `armv7-linux-androideabi`.
+pub(crate) fn clang_target(rustc_target: &str, api_level: u32) -> String {
+    let triple = match rustc_target {
+        "armv7-linux-androideabi" | "thumbv7neon-linux-androideabi" => "armv7a-linux-androideabi",
+        triple => triple,
+    };
+    format!("{triple}{api_level}")
+}
+
+/// Parse `ANDROID_PLATFORM`, which is e.g. `android-21` as with CMake and
+/// `ndk-build`, or just `21`.
+pub(crate) fn parse_platform(platform: &str) -> Option<u32> {
+    platform
+        .strip_prefix("android-")
+        .unwrap_or(platform)
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::{clang_target, parse_platform, AndroidNdk};
+
+    fn write_ndk(path: &Path, revision: &str) {
+        fs::create_dir_all(path.join("toolchains/llvm/prebuilt/linux-x86_64/bin")).unwrap();
+        fs::write(
+            path.join("source.properties"),
+            format!("Pkg.Desc = Android NDK\nPkg.Revision = {revision}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn find_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_ndk(&root.join("25.2.9519653"), "25.2.9519653");
+        write_ndk(&root.join("26.1.10909125"), "26.1.10909125");
+        write_ndk(&root.join("9.0.0"), "9.0.0");
+        fs::create_dir(root.join("not-an-ndk")).unwrap();
+
+        let ndk = AndroidNdk::find_newest(root).unwrap();
+        assert_eq!(ndk.path, root.join("26.1.10909125"));
+        assert_eq!(ndk.min_api_level, 21);
+        assert_eq!(
+            ndk.bin_dir("x86_64-unknown-linux-gnu"),
+            Some(root.join("26.1.10909125/toolchains/llvm/prebuilt/linux-x86_64/bin"))
+        );
+        assert_eq!(ndk.bin_dir("aarch64-apple-darwin"), None);
+
+        let ndk = AndroidNdk::read(&root.join("25.2.9519653")).unwrap();
+        assert_eq!(ndk.min_api_level, 19);
+        fs::create_dir(root.join("25.2.9519653/meta")).unwrap();
+        fs::write(
+            root.join("25.2.9519653/meta/platforms.json"),
+            r#"{"min": 23, "max": 33, "aliases": {"20": 19}}"#,
+        )
+        .unwrap();
+        let ndk = AndroidNdk::read(&root.join("25.2.9519653")).unwrap();
+        assert_eq!(ndk.min_api_level, 23);
+    }
+
+    #[test]
+    fn targets() {
+        assert_eq!(
+            clang_target("aarch64-linux-android", 21),
+            "aarch64-linux-android21"
+        );
+        assert_eq!(
+            clang_target("armv7-linux-androideabi", 19),
+            "armv7a-linux-androideabi19"
+        );
+        assert_eq!(parse_platform("android-24"), Some(24));
+        assert_eq!(parse_platform("30"), Some(30));
+        assert_eq!(parse_platform("latest"), None);
+    }
+}
@@ -0,0 +1,205 @@
+//! Discovery of Apple SDKs from their `SDKSettings.json`, without `xcrun`.
+//!
+//! This is what makes it possible to cross-compile to Apple targets from
+//! other hosts, where SDKs are usually unpacked somewhere by hand or by
+//! osxcross, and listed in `APPLE_SDKS_DIR`.
+
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::json;
+
+/// An SDK, e.g. `MacOSX14.2.sdk`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AppleSdk {
+    pub(crate) path: PathBuf,
+    /// The version of the SDK, e.g. `14.2`.
+    pub(crate) version: Box<str>,
+    /// The deployment target that Xcode uses by default with the SDK.
+    pub(crate) default_deployment_target: Option<Box<str>>,
+    /// The lowest deployment target that the SDK supports.
+    pub(crate) minimum_deployment_target: Option<Box<str>>,
+}
+
+impl AppleSdk {
+    /// Read the SDK at `path`, if it is an SDK for `sdk_name`, the name of
+    /// the platform as passed to `xcrun --sdk`, e.g. `iphonesimulator`.
+    ///
+    /// The deployment targets are those of `supported_target`, which is
+    /// usually `sdk_name` again, or `iosmac` for Mac Catalyst.
+    pub(crate) fn read(path: &Path, sdk_name: &str, supported_target: &str) -> Option<AppleSdk> {
+        let settings = fs::read_to_string(path.join("SDKSettings.json")).ok()?;
+        let settings = json::parse(&settings).ok()?;
+        let str_field = |value: &json::Value, key: &str| -> Option<Box<str>> {
+            value.get(key)?.as_str().map(Box::from)
+        };
+
+        // `CanonicalName` is the platform followed by the version, e.g.
+        // `macosx14.2`.
+        let platform = settings
+            .get("DefaultProperties")
+            .and_then(|properties| properties.get("PLATFORM_NAME")?.as_str())
+            .or_else(|| {
+                let name = settings.get("CanonicalName")?.as_str()?;
+                Some(name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.'))
+            })?;
+        if platform != sdk_name {
+            return None;
+        }
+
+        let version = str_field(&settings, "Version")?;
+        let target = settings
+            .get("SupportedTargets")
+            .and_then(|targets| targets.get(supported_target));
+        let mut default_deployment_target =
+            target.and_then(|target| str_field(target, "DefaultDeploymentTarget"));
+        if supported_target == sdk_name {
+            default_deployment_target = default_deployment_target
+                .or_else(|| str_field(&settings, "DefaultDeploymentTarget"))
+                .or_else(|| Some(version.clone()));
+        }
+        Some(AppleSdk {
+            path: path.to_path_buf(),
+            version,
+            default_deployment_target,
+            minimum_deployment_target: target
+                .and_then(|target| str_field(target, "MinimumDeploymentTarget")),
+        })
+    }
+
+    /// Find the newest SDK for `sdk_name` in `roots`.
+    ///
+    /// A root may be an SDK itself, a directory containing SDKs, an osxcross
+    /// target directory with SDKs in `SDK/`, or an Xcode `Developer`
+    /// directory with SDKs in `Platforms/*.platform/Developer/SDKs/`.
+    pub(crate) fn find(
+        roots: impl IntoIterator<Item = PathBuf>,
+        sdk_name: &str,
+        supported_target: &str,
+    ) -> Option<AppleSdk> {
+        let mut candidates = Vec::new();
+        for root in roots {
+            candidates.push(root.clone());
+            candidates.extend(sdk_dirs(&root));
+            candidates.extend(sdk_dirs(&root.join("SDK")));
+            for dir in [root.clone(), root.join("Platforms")] {
+                for platform in dirs_with_extension(&dir, "platform") {
+                    candidates.extend(sdk_dirs(&platform.join("Developer/SDKs")));
+                }
+            }
+        }
+
+        candidates
+            .iter()
+            .filter_map(|path| AppleSdk::read(path, sdk_name, supported_target))
+            // Prefer the first of equal versions, which is in an earlier root.
+            .rev()
+            .max_by(|a, b| compare_versions(&a.version, &b.version))
+    }
+}
+
+fn sdk_dirs(dir: &Path) -> Vec<PathBuf> {
+    dirs_with_extension(dir, "sdk")
+}
+
+fn dirs_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut dirs: Vec<_> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == extension) && path.is_dir())
+        .collect();
+    // `read_dir` is in no particular order.
+    dirs.sort();
+    dirs
+}
+
+/// Compare dotted versions numerically, e.g. `10.9` < `10.13`. Missing
+/// components count as zero.
+pub(crate) fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.').map(|n| n.parse::<u32>().unwrap_or(0));
+    let mut b = b.split('.').map(|n| n.parse::<u32>().unwrap_or(0));
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (a, b) => match a.unwrap_or(0).cmp(&b.unwrap_or(0)) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use std::fs;
+    use std::path::Path;
+
+    use super::{compare_versions, AppleSdk};
+
+    fn write_sdk(path: &Path, canonical_name: &str, version: &str) {
+        fs::create_dir_all(path).unwrap();
+        fs::write(
+            path.join("SDKSettings.json"),
+            format!(
+                r#"{{
+                    "CanonicalName": "{canonical_name}",
+                    "Version": "{version}",
+                    "SupportedTargets": {{
+                        "macosx": {{
+                            "DefaultDeploymentTarget": "{version}",
+                            "MinimumDeploymentTarget": "10.13"
+                        }},
+                        "iosmac": {{
+                            "DefaultDeploymentTarget": "17.2",
+                            "MinimumDeploymentTarget": "13.1"
+                        }}
+                    }}
+                }}"#
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn find() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+
+        write_sdk(&root.join("SDK/MacOSX13.3.sdk"), "macosx13.3", "13.3");
+        write_sdk(&root.join("SDK/MacOSX14.2.sdk"), "macosx14.2", "14.2");
+        let xcode_sdk = root.join("Platforms/iPhoneSimulator.platform/Developer/SDKs");
+        write_sdk(
+            &xcode_sdk.join("iPhoneSimulator17.2.sdk"),
+            "iphonesimulator17.2",
+            "17.2",
+        );
+
+        let sdk = AppleSdk::find([root.clone()], "macosx", "macosx").unwrap();
+        assert_eq!(sdk.path, root.join("SDK/MacOSX14.2.sdk"));
+        assert_eq!(&*sdk.version, "14.2");
+        assert_eq!(sdk.default_deployment_target.as_deref(), Some("14.2"));
+        assert_eq!(sdk.minimum_deployment_target.as_deref(), Some("10.13"));
+
+        let sdk = AppleSdk::find([root.clone()], "macosx", "iosmac").unwrap();
+        assert_eq!(sdk.default_deployment_target.as_deref(), Some("17.2"));
+        assert_eq!(sdk.minimum_deployment_target.as_deref(), Some("13.1"));
+
+        let sdk = AppleSdk::find([root.clone()], "iphonesimulator", "iphonesimulator").unwrap();
+        assert_eq!(sdk.path, xcode_sdk.join("iPhoneSimulator17.2.sdk"));
+        assert_eq!(sdk.minimum_deployment_target, None);
+
+        assert_eq!(AppleSdk::find([root], "iphoneos", "iphoneos"), None);
+    }
+
+    #[test]
+    fn versions() {
+        assert_eq!(compare_versions("10.9", "10.13"), Ordering::Less);
+        assert_eq!(compare_versions("14.0", "14"), Ordering::Equal);
+        assert_eq!(compare_versions("14.2.1", "14.2"), Ordering::Greater);
+    }
+}
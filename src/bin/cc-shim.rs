@@ -15,6 +15,41 @@ fn main() -> ExitCode {
     let mut args = args.iter();
     let program = args.next().expect("Unexpected empty args");
 
+    // Target triple queries are answered without being recorded, so that they
+    // don't shift the `outN` files that tests inspect.
+    if args
+        .clone()
+        .any(|a| a == "-dumpmachine" || a == "-print-target-triple")
+    {
+        if let Some(triple) = env::var_os("CC_SHIM_TARGET_TRIPLE") {
+            println!("{}", triple.to_string_lossy());
+        }
+        return ExitCode::SUCCESS;
+    }
+    // Answer compiler family detection like the compiler being simulated.
+    // Otherwise it fails, so that the family is guessed from the name.
+    if args
+        .clone()
+        .any(|a| a.ends_with("detect_compiler_family.c"))
+    {
+        return match env::var_os("CC_SHIM_DETECT_FAMILY") {
+            Some(output) => {
+                println!("{}", output.to_string_lossy());
+                ExitCode::SUCCESS
+            }
+            None => ExitCode::FAILURE,
+        };
+    }
+    if args.clone().any(|a| a == "--version") {
+        return match env::var_os("CC_SHIM_VERSION") {
+            Some(version) => {
+                println!("{}", version.to_string_lossy());
+                ExitCode::SUCCESS
+            }
+            None => ExitCode::FAILURE,
+        };
+    }
+
     let out_dir = PathBuf::from(
         env::var_os("CC_SHIM_OUT_DIR")
             .unwrap_or_else(|| panic!("{}: CC_SHIM_OUT_DIR not found", program)),
@@ -63,6 +98,15 @@ fn main() -> ExitCode {
         )
     });
 
+    // Simulate a compile error for the given source file.
+    if let Some(source) = env::var_os("CC_SHIM_FAIL_ON") {
+        let source = source.to_string_lossy();
+        if args.clone().any(|a| a.ends_with(&*source)) {
+            eprintln!("{source}:1:1: error: simulated failure");
+            return ExitCode::FAILURE;
+        }
+    }
+
     if program.starts_with("clang") {
         // Validate that we got no `-?` without a preceding `--driver-mode=cl`. Compiler family
         // detection depends on this.
@@ -72,7 +116,7 @@ fn main() -> ExitCode {
                 .take(cl_like_help_option_idx)
                 .rev()
                 .find_map(|a| a.strip_prefix("--driver-mode="))
-                .is_some_and(|a| a == "cl");
+                == Some("cl");
             if has_cl_clang_driver_before_cl_like_help_option {
                 return ExitCode::SUCCESS;
             } else {
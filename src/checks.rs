@@ -0,0 +1,282 @@
+//! Autoconf-style feature checks.
+//!
+//! Vendored C projects that come with an autotools-based build usually expect a
+//! generated `config.h` describing the headers, functions and types that
+//! are available on the target. [`Checks`] runs the same kind of probes
+//! using the compiler configured on a [`Build`], and writes the results
+//! out as a `config.h` in `OUT_DIR`.
+//!
+//! All checks only compile code (except [`Checks::check_function`], which
+//! also links), so they work when cross-compiling.
+//!
+//! # Example
+//!
+//! ```no_run
+//! let mut build = cc::Build::new();
+//!
+//! let mut checks = cc::checks::Checks::new(&build);
+//! checks.check_header("unistd.h").unwrap();
+//! checks.check_function("strlcpy").unwrap();
+//! checks.check_type_size("long").unwrap();
+//! checks.check_struct_member("struct stat", "st_mtim", "sys/stat.h").unwrap();
+//! checks.write_config_h(&mut build, "config.h").unwrap();
+//!
+//! build.file("src/foo.c").compile("foo");
+//! ```
+
+use std::collections::hash_map;
+use std::fmt::Write as _;
+use std::hash::Hasher;
+use std::path::PathBuf;
+
+use crate::utilities::write_if_changed;
+use crate::{Build, Error, ErrorKind};
+
+/// The largest type size (in bytes) that [`Checks::check_type_size`] will
+/// probe for before giving up.
+const MAX_TYPE_SIZE: usize = 1 << 16;
+
+/// A set of feature checks, and the `config.h` defines resulting from them.
+///
+/// See the [module-level documentation](self) for an example.
+#[derive(Clone, Debug)]
+pub struct Checks {
+    build: Build,
+    headers: Vec<Box<str>>,
+    defines: Vec<(Box<str>, Option<Box<str>>)>,
+}
+
+impl Checks {
+    /// Create a new set of checks, which will be run with the compiler and
+    /// flags configured on `build`.
+    ///
+    /// Files set on `build` are ignored, and changes made to `build` after
+    /// this call are not taken into account.
+    pub fn new(build: &Build) -> Checks {
+        let mut build = build.clone();
+        build.files.clear();
+        build.cargo_warnings(false);
+        Checks {
+            build,
+            headers: Vec::new(),
+            defines: Vec::new(),
+        }
+    }
+
+    /// Check whether `header` can be included, defining `HAVE_<HEADER>`
+    /// (e.g. `HAVE_SYS_TYPES_H` for `sys/types.h`).
+    ///
+    /// Headers that are found are included by the later checks of
+    /// [`check_type_size`](Self::check_type_size).
+    pub fn check_header(&mut self, header: &str) -> Result<bool, Error> {
+        let code = format!("#include <{header}>\n");
+        let found = self.compiles(&code, false)?;
+        if found && !self.headers.iter().any(|h| &**h == header) {
+            self.headers.push(header.into());
+        }
+        self.record(format!("HAVE_{}", macro_name(header)), found);
+        Ok(found)
+    }
+
+    /// Check whether `function` can be linked against, defining
+    /// `HAVE_<FUNCTION>`.
+    ///
+    /// Like autoconf's `AC_CHECK_FUNC`, this declares the function itself
+    /// instead of including a header, so it only checks that the symbol
+    /// exists in the libraries linked by default.
+    pub fn check_function(&mut self, function: &str) -> Result<bool, Error> {
+        let code = format!(
+            "#ifdef __cplusplus\n\
+             extern \"C\"\n\
+             #endif\n\
+             char {function}(void);\n\
+             int main(void) {{ return {function}(); }}\n"
+        );
+        let found = self.compiles(&code, true)?;
+        self.record(format!("HAVE_{}", macro_name(function)), found);
+        Ok(found)
+    }
+
+    /// Check whether `symbol` is declared (as a function, variable or macro)
+    /// by `header`, defining `HAVE_<SYMBOL>`.
+    pub fn check_symbol_in_header(&mut self, symbol: &str, header: &str) -> Result<bool, Error> {
+        let code = format!(
+            "#include <{header}>\n\
+             int main(void) {{\n\
+             #ifndef {symbol}\n\
+             (void){symbol};\n\
+             #endif\n\
+             return 0;\n\
+             }}\n"
+        );
+        let found = self.compiles(&code, false)?;
+        self.record(format!("HAVE_{}", macro_name(symbol)), found);
+        Ok(found)
+    }
+
+    /// Determine the size of `ty` in bytes, defining `SIZEOF_<TYPE>` (e.g.
+    /// `SIZEOF_UNSIGNED_LONG` or `SIZEOF_VOID_P` for `void *`).
+    ///
+    /// The size is found without running any code on the target, by
+    /// compiling array declarations that are only valid for certain sizes.
+    ///
+    /// Returns `None` (and leaves the define unset) if the type does not
+    /// exist. `<stddef.h>` and every header found by
+    /// [`check_header`](Self::check_header) are included beforehand.
+    pub fn check_type_size(&mut self, ty: &str) -> Result<Option<usize>, Error> {
+        let name = format!("SIZEOF_{}", macro_name(ty));
+        let size = self.type_size(ty)?;
+        self.record_value(name, size.map(|size| size.to_string()));
+        Ok(size)
+    }
+
+    fn type_size(&self, ty: &str) -> Result<Option<usize>, Error> {
+        let mut prologue = String::from("#include <stddef.h>\n");
+        for header in &self.headers {
+            let _ = writeln!(prologue, "#include <{header}>");
+        }
+        let size_at_most = |n: usize| {
+            self.compiles(
+                &format!("{prologue}typedef char cc_check_size[(sizeof({ty}) <= {n}) ? 1 : -1];\n"),
+                false,
+            )
+        };
+
+        if !self.compiles(
+            &format!("{prologue}typedef char cc_check_size[sizeof({ty}) ? 1 : -1];\n"),
+            false,
+        )? {
+            return Ok(None);
+        }
+
+        // Find an upper bound, then bisect down to the exact size.
+        let mut hi = 1;
+        while !size_at_most(hi)? {
+            if hi >= MAX_TYPE_SIZE {
+                return Err(Error::new(
+                    ErrorKind::InvalidArgument,
+                    format!("size of `{ty}` is larger than {MAX_TYPE_SIZE} bytes"),
+                ));
+            }
+            hi *= 2;
+        }
+        let mut lo = hi / 2 + 1;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if size_at_most(mid)? {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        Ok(Some(hi))
+    }
+
+    /// Check whether the struct (or union) `ty` declared in `header` has a
+    /// field named `member`, defining `HAVE_<TY>_<MEMBER>` (e.g.
+    /// `HAVE_STRUCT_STAT_ST_MTIM`).
+    pub fn check_struct_member(
+        &mut self,
+        ty: &str,
+        member: &str,
+        header: &str,
+    ) -> Result<bool, Error> {
+        let code = format!(
+            "#include <{header}>\n\
+             int main(void) {{ return (int)sizeof((({ty} *)0)->{member}); }}\n"
+        );
+        let found = self.compiles(&code, false)?;
+        self.record(
+            format!("HAVE_{}_{}", macro_name(ty), macro_name(member)),
+            found,
+        );
+        Ok(found)
+    }
+
+    /// Add a define with a fixed value to the generated `config.h`.
+    pub fn define(&mut self, name: &str, value: &str) -> &mut Checks {
+        self.record_value(name.into(), Some(value.into()));
+        self
+    }
+
+    /// Write the results of all checks run so far to `file_name` in
+    /// `OUT_DIR`, and add `OUT_DIR` to the include path of `build`.
+    ///
+    /// The file is left untouched if its contents did not change, so that
+    /// its modification time stays stable across build script runs.
+    ///
+    /// Returns the path to the written file.
+    pub fn write_config_h(&self, build: &mut Build, file_name: &str) -> Result<PathBuf, Error> {
+        let out_dir = self.build.get_out_dir()?.into_owned();
+        let path = out_dir.join(file_name);
+        write_if_changed(&path, self.config_h().as_bytes())?;
+        build.include(&out_dir);
+        Ok(path)
+    }
+
+    /// The contents of the `config.h` that [`write_config_h`](Self::write_config_h)
+    /// would write.
+    pub fn config_h(&self) -> String {
+        let mut s = String::from("/* Generated by the `cc` crate. Do not edit. */\n\n");
+        for (name, value) in &self.defines {
+            let _ = match value {
+                Some(value) => writeln!(s, "#define {name} {value}"),
+                None => writeln!(s, "/* #undef {name} */"),
+            };
+        }
+        s
+    }
+
+    fn record(&mut self, name: String, found: bool) {
+        self.record_value(name, found.then(|| "1".to_string()));
+    }
+
+    fn record_value(&mut self, name: String, value: Option<String>) {
+        let value = value.map(Into::into);
+        match self.defines.iter_mut().find(|(n, _)| **n == *name) {
+            Some((_, v)) => *v = value,
+            None => self.defines.push((name.into(), value)),
+        }
+    }
+
+    fn compiles(&self, code: &str, link: bool) -> Result<bool, Error> {
+        let mut hasher = hash_map::DefaultHasher::new();
+        hasher.write(code.as_bytes());
+        hasher.write_u8(link as u8);
+        let name = format!("cc_check_{:016x}", hasher.finish());
+        self.build.try_compile_check(&name, code, link)
+    }
+}
+
+/// Turn a header, function or type name into the conventional macro name
+/// suffix, e.g. `sys/types.h` -> `SYS_TYPES_H` and `void *` -> `VOID_P`.
+fn macro_name(s: &str) -> String {
+    let mut name = String::with_capacity(s.len());
+    for c in s.trim().chars() {
+        match c {
+            '*' => name.push('P'),
+            c if c.is_ascii_alphanumeric() => name.push(c.to_ascii_uppercase()),
+            _ if name.ends_with('_') || name.is_empty() => {}
+            _ => name.push('_'),
+        }
+    }
+    while name.ends_with('_') {
+        name.pop();
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::macro_name;
+
+    #[test]
+    fn macro_names() {
+        assert_eq!(macro_name("sys/types.h"), "SYS_TYPES_H");
+        assert_eq!(macro_name("strlcpy"), "STRLCPY");
+        assert_eq!(macro_name("unsigned long long"), "UNSIGNED_LONG_LONG");
+        assert_eq!(macro_name("void *"), "VOID_P");
+        assert_eq!(macro_name("char **"), "CHAR_PP");
+        assert_eq!(macro_name("struct stat"), "STRUCT_STAT");
+    }
+}
@@ -0,0 +1,220 @@
+//! Translation of compiler arguments into a form suitable for `libclang`,
+//! e.g. for bindgen's `clang_args`.
+
+use std::ffi::OsString;
+
+/// Flags whose value is passed as the next argument, and which affect how
+/// headers are parsed.
+const FLAGS_WITH_VALUE: &[&str] = &[
+    "-I",
+    "-D",
+    "-U",
+    "-isystem",
+    "-iquote",
+    "-idirafter",
+    "-include",
+    "-imacros",
+    "-isysroot",
+    "--sysroot",
+    "-target",
+    "-arch",
+    "-x",
+    "-Xclang",
+];
+
+/// `-f` flags that only influence code generation or diagnostics.
+const CODEGEN_ONLY_F_FLAGS: &[&str] = &[
+    "-ffunction-sections",
+    "-fdata-sections",
+    "-fpic",
+    "-fPIC",
+    "-fpie",
+    "-fPIE",
+    "-fno-plt",
+    "-fomit-frame-pointer",
+    "-fno-omit-frame-pointer",
+    "-fstack-protector",
+    "-fno-stack-protector",
+    "-fembed-bitcode",
+    "-fdebug-prefix-map",
+    "-ffile-prefix-map",
+    "-fmacro-prefix-map",
+    "-fcolor-diagnostics",
+    "-fdiagnostics-color",
+    "-fsanitize",
+    "-fno-sanitize",
+];
+
+/// Flags without a value that affect how headers are parsed.
+const STANDALONE_FLAGS: &[&str] = &[
+    "-nostdinc",
+    "-nostdinc++",
+    "-ansi",
+    "-pthread",
+    "-trigraphs",
+];
+
+/// Translate `args` (as passed to a compiler of the MSVC family if `msvc`)
+/// into Clang driver arguments, dropping those that only affect code
+/// generation.
+pub(crate) fn translate(args: &[OsString], msvc: bool) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut args = args.iter().map(|arg| arg.to_string_lossy());
+    while let Some(arg) = args.next() {
+        // Flags meant for the host compiler of nvcc.
+        if arg == "-Xcompiler" {
+            continue;
+        }
+
+        // MSVC accepts both `-` and `/` as the flag prefix.
+        let arg = match arg.strip_prefix('/') {
+            Some(rest) if msvc => format!("-{rest}"),
+            _ => arg.into_owned(),
+        };
+
+        if FLAGS_WITH_VALUE.contains(&&*arg) {
+            if let Some(value) = args.next() {
+                out.push(arg);
+                out.push(value.into_owned());
+            }
+        } else if msvc && arg.starts_with("-FI") {
+            let file = match &arg[3..] {
+                "" => args.next().map(|value| value.into_owned()),
+                file => Some(file.to_string()),
+            };
+            if let Some(file) = file {
+                out.push("-include".to_string());
+                out.push(file);
+            }
+        } else if let Some(std) = arg.strip_prefix("-std:").filter(|_| msvc) {
+            let std = match std {
+                "c++latest" => "c++2b",
+                "clatest" => "c2x",
+                std => std,
+            };
+            out.push(format!("-std={std}"));
+        } else if msvc && matches!(&*arg, "-MD" | "-MDd" | "-MT" | "-MTd") {
+            // The runtime library selection is visible to headers.
+            out.push("-D_MT".to_string());
+            if arg.starts_with("-MD") {
+                out.push("-D_DLL".to_string());
+            }
+            if arg.ends_with('d') {
+                out.push("-D_DEBUG".to_string());
+            }
+        } else if [
+            "-I",
+            "-D",
+            "-U",
+            "-std=",
+            "--target=",
+            "--sysroot=",
+            "--gcc-toolchain=",
+            "-stdlib=",
+        ]
+        .iter()
+        .any(|prefix| arg.starts_with(prefix))
+            || STANDALONE_FLAGS.contains(&&*arg)
+            // `-m` and `-f` flags are also MSVC flags with different meanings.
+            || (!msvc && arg.starts_with("-m"))
+            || (!msvc
+                && arg.starts_with("-f")
+                && !CODEGEN_ONLY_F_FLAGS
+                    .iter()
+                    .any(|flag| arg == *flag || arg.starts_with(&format!("{flag}="))))
+        {
+            out.push(arg);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::translate;
+    use std::ffi::OsString;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn gnu() {
+        assert_eq!(
+            translate(
+                &args(&[
+                    "-O2",
+                    "-ffunction-sections",
+                    "-fdata-sections",
+                    "-fPIC",
+                    "-g",
+                    "-gdwarf-4",
+                    "-fno-omit-frame-pointer",
+                    "--target=aarch64-unknown-linux-gnu",
+                    "-mmacosx-version-min=10.12",
+                    "-isysroot",
+                    "/sdk",
+                    "-I",
+                    "include",
+                    "-Wall",
+                    "-Wextra",
+                    "-DFOO=1",
+                    "-std=c11",
+                    "-fno-exceptions",
+                    "-fsanitize=address",
+                    "-c",
+                ]),
+                false,
+            ),
+            [
+                "--target=aarch64-unknown-linux-gnu",
+                "-mmacosx-version-min=10.12",
+                "-isysroot",
+                "/sdk",
+                "-I",
+                "include",
+                "-DFOO=1",
+                "-std=c11",
+                "-fno-exceptions",
+            ]
+        );
+    }
+
+    #[test]
+    fn msvc() {
+        assert_eq!(
+            translate(
+                &args(&[
+                    "-nologo",
+                    "-MD",
+                    "-O2",
+                    "-Z7",
+                    "-Brepro",
+                    "/std:c++latest",
+                    "-std:c11",
+                    "-I",
+                    "include",
+                    "/Iother",
+                    "/DFOO",
+                    "-FIforced.h",
+                    "-W4",
+                    "-EHsc",
+                    "-fp:fast",
+                ]),
+                true,
+            ),
+            [
+                "-D_MT",
+                "-D_DLL",
+                "-std=c++2b",
+                "-std=c11",
+                "-I",
+                "include",
+                "-Iother",
+                "-DFOO",
+                "-include",
+                "forced.h",
+            ]
+        );
+    }
+}
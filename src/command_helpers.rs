@@ -7,16 +7,20 @@ use std::{
     fmt::Display,
     fs,
     hash::Hasher,
-    io::{self, Read, Write},
+    io::{self, Read},
     path::Path,
     process::{Child, ChildStderr, Command, Output, Stdio},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    thread,
 };
 
-use crate::{utilities::cargo_env_var_os, Error, ErrorKind, Object};
+use crate::{
+    utilities::cargo_env_var_os, CommandExecutor, CommandKind, DefaultExecutor, Error, ErrorKind,
+    Object, OutputSink, StdoutSink,
+};
 
 #[derive(Clone, Debug)]
 pub(crate) struct CargoOutput {
@@ -24,6 +28,10 @@ pub(crate) struct CargoOutput {
     pub(crate) warnings: bool,
     pub(crate) debug: bool,
     pub(crate) output: OutputKind,
+    /// Where output goes, [`StdoutSink`] if `None`.
+    pub(crate) sink: Option<Arc<dyn OutputSink>>,
+    /// What starts commands, [`DefaultExecutor`] if `None`.
+    pub(crate) executor: Option<Arc<dyn CommandExecutor>>,
     checked_dbg_var: Arc<AtomicBool>,
 }
 
@@ -45,6 +53,8 @@ impl CargoOutput {
             metadata: true,
             warnings: true,
             output: OutputKind::Forward,
+            sink: None,
+            executor: None,
             debug: match std::env::var_os("CC_ENABLE_DEBUG_OUTPUT") {
                 Some(v) => v != "0" && v != "false" && !v.is_empty(),
                 None => false,
@@ -53,15 +63,29 @@ impl CargoOutput {
         }
     }
 
+    fn sink(&self) -> &dyn OutputSink {
+        match &self.sink {
+            Some(sink) => &**sink,
+            None => &StdoutSink,
+        }
+    }
+
+    pub(crate) fn executor(&self) -> &dyn CommandExecutor {
+        match &self.executor {
+            Some(executor) => &**executor,
+            None => &DefaultExecutor,
+        }
+    }
+
     pub(crate) fn print_metadata(&self, s: &dyn Display) {
         if self.metadata {
-            println!("{s}");
+            self.sink().metadata(&s.to_string());
         }
     }
 
     pub(crate) fn print_warning(&self, arg: &dyn Display) {
         if self.warnings {
-            println!("cargo:warning={arg}");
+            self.sink().warning(&arg.to_string());
         }
     }
 
@@ -72,13 +96,24 @@ impl CargoOutput {
                 .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
                 .is_ok()
         {
-            println!("cargo:rerun-if-env-changed=CC_ENABLE_DEBUG_OUTPUT");
+            self.sink()
+                .metadata("cargo:rerun-if-env-changed=CC_ENABLE_DEBUG_OUTPUT");
         }
         if self.debug {
-            println!("{arg}");
+            self.sink().debug(&arg.to_string());
         }
     }
 
+    /// Forward output that a command printed to stdout.
+    pub(crate) fn print_compiler_stdout(&self, output: &[u8]) {
+        self.sink().compiler_stdout(output);
+    }
+
+    /// Forward a line that a command printed to stderr.
+    pub(crate) fn print_compiler_stderr(&self, line: &[u8]) {
+        self.sink().compiler_stderr(line);
+    }
+
     fn stdio_for_warnings(&self) -> Stdio {
         if self.warnings {
             Stdio::piped()
@@ -90,6 +125,8 @@ impl CargoOutput {
     fn stdio_for_output(&self) -> Stdio {
         match self.output {
             OutputKind::Capture => Stdio::piped(),
+            // A custom sink gets the output through a pipe, see `StdoutForwarder`.
+            OutputKind::Forward if self.sink.is_some() => Stdio::piped(),
             OutputKind::Forward => Stdio::inherit(),
             OutputKind::Discard => Stdio::null(),
         }
@@ -104,18 +141,23 @@ pub(crate) struct StderrForwarder {
     bytes_available_failed: bool,
     /// number of bytes buffered in inner
     bytes_buffered: usize,
+    /// Forwarded lines, if they should be kept.
+    captured: Option<Vec<u8>>,
+    cargo_output: CargoOutput,
 }
 
 const MIN_BUFFER_CAPACITY: usize = 100;
 
 impl StderrForwarder {
-    pub(crate) fn new(child: &mut Child) -> Self {
+    pub(crate) fn new(child: &mut Child, cargo_output: &CargoOutput) -> Self {
         Self {
             inner: child
                 .stderr
                 .take()
                 .map(|stderr| (stderr, Vec::with_capacity(MIN_BUFFER_CAPACITY))),
             bytes_buffered: 0,
+            captured: None,
+            cargo_output: cargo_output.clone(),
             #[cfg(feature = "parallel")]
             is_non_blocking: false,
             #[cfg(feature = "parallel")]
@@ -147,7 +189,7 @@ impl StderrForwarder {
                             // On Windows, if we get an error then the pipe is broken, so flush
                             // the buffer and bail.
                             if !buffer.is_empty() {
-                                write_warning(&buffer[..]);
+                                forward_line(&mut self.captured, &self.cargo_output, &buffer[..]);
                             }
                             self.inner = None;
                             break true;
@@ -187,7 +229,7 @@ impl StderrForwarder {
                             // Only forward complete lines, leave the rest in the buffer.
                             if let Some((b'\n', line)) = line.split_last() {
                                 consumed += line.len() + 1;
-                                write_warning(line);
+                                forward_line(&mut self.captured, &self.cargo_output, line);
                             }
                         }
                         if consumed > 0 && consumed < self.bytes_buffered {
@@ -199,10 +241,16 @@ impl StderrForwarder {
                     res => {
                         // End of stream: flush remaining data and bail.
                         if self.bytes_buffered > 0 {
-                            write_warning(&buffer[..self.bytes_buffered]);
+                            forward_line(
+                                &mut self.captured,
+                                &self.cargo_output,
+                                &buffer[..self.bytes_buffered],
+                            );
                         }
                         if let Err(err) = res {
-                            write_warning(
+                            forward_line(
+                                &mut self.captured,
+                                &self.cargo_output,
                                 format!("Failed to read from child stderr: {err}").as_bytes(),
                             );
                         }
@@ -216,6 +264,16 @@ impl StderrForwarder {
         }
     }
 
+    /// Keep the forwarded output, to be retrieved with
+    /// [`take_captured`](Self::take_captured).
+    pub(crate) fn capture(&mut self) {
+        self.captured = Some(Vec::new());
+    }
+
+    pub(crate) fn take_captured(&mut self) -> Vec<u8> {
+        self.captured.take().unwrap_or_default()
+    }
+
     #[cfg(feature = "parallel")]
     pub(crate) fn set_non_blocking(&mut self) -> Result<(), Error> {
         assert!(!self.is_non_blocking);
@@ -241,21 +299,66 @@ impl StderrForwarder {
     }
 }
 
-fn write_warning(line: &[u8]) {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    stdout.write_all(b"cargo:warning=").unwrap();
-    stdout.write_all(line).unwrap();
-    stdout.write_all(b"\n").unwrap();
+fn forward_line(captured: &mut Option<Vec<u8>>, cargo_output: &CargoOutput, line: &[u8]) {
+    cargo_output.print_compiler_stderr(line);
+    if let Some(captured) = captured {
+        captured.extend_from_slice(line);
+        captured.push(b'\n');
+    }
+}
+
+/// Reads the stdout of a child on a separate thread if it was piped for a
+/// custom [`OutputSink`], to pass it on once the child has exited.
+pub(crate) struct StdoutForwarder(Option<thread::JoinHandle<Vec<u8>>>);
+
+impl StdoutForwarder {
+    pub(crate) fn new(child: &mut Child, cargo_output: &CargoOutput) -> Self {
+        let stdout = match cargo_output.output {
+            OutputKind::Forward => child.stdout.take(),
+            _ => None,
+        };
+        Self(stdout.map(|mut stdout| {
+            thread::spawn(move || {
+                let mut output = Vec::new();
+                let _ = stdout.read_to_end(&mut output);
+                output
+            })
+        }))
+    }
+
+    pub(crate) fn forward(self, cargo_output: &CargoOutput) {
+        if let Some(Ok(output)) = self.0.map(thread::JoinHandle::join) {
+            if !output.is_empty() {
+                cargo_output.print_compiler_stdout(&output);
+            }
+        }
+    }
 }
 
+/// Wait for `child` while forwarding its output. If `capture_diagnostics`,
+/// errors include everything the command printed to stderr.
 fn wait_on_child(
     cmd: &Command,
     child: &mut Child,
     cargo_output: &CargoOutput,
+    capture_diagnostics: bool,
 ) -> Result<(), Error> {
-    StderrForwarder::new(child).forward_all();
+    let stdout_forwarder = StdoutForwarder::new(child, cargo_output);
+    let mut stderr_forwarder = StderrForwarder::new(child, cargo_output);
+    if capture_diagnostics {
+        stderr_forwarder.capture();
+    }
+    stderr_forwarder.forward_all();
+    let result = wait_for_status(cmd, child, cargo_output);
+    stdout_forwarder.forward(cargo_output);
+    result.map_err(|err| with_diagnostics(err, &stderr_forwarder.take_captured()))
+}
 
+fn wait_for_status(
+    cmd: &Command,
+    child: &mut Child,
+    cargo_output: &CargoOutput,
+) -> Result<(), Error> {
     let status = match child.wait() {
         Ok(s) => s,
         Err(e) => {
@@ -340,19 +443,45 @@ pub(crate) fn objects_from_files(files: &[Arc<Path>], dst: &Path) -> Result<Vec<
     Ok(objects)
 }
 
-pub(crate) fn run(cmd: &mut Command, cargo_output: &CargoOutput) -> Result<(), Error> {
-    let mut child = spawn(cmd, cargo_output)?;
-    wait_on_child(cmd, &mut child, cargo_output)
+pub(crate) fn run(
+    cmd: &mut Command,
+    cargo_output: &CargoOutput,
+    kind: CommandKind,
+) -> Result<(), Error> {
+    let mut child = spawn(cmd, cargo_output, kind)?;
+    wait_on_child(cmd, &mut child, cargo_output, false)
+}
+
+/// Like [`run`], but if the command fails, the error includes everything it
+/// printed to stderr.
+pub(crate) fn run_with_diagnostics(
+    cmd: &mut Command,
+    cargo_output: &CargoOutput,
+    kind: CommandKind,
+) -> Result<(), Error> {
+    let mut child = spawn(cmd, cargo_output, kind)?;
+    wait_on_child(cmd, &mut child, cargo_output, true)
+}
+
+/// Append the `diagnostics` printed by a failed command to `err`.
+pub(crate) fn with_diagnostics(err: Error, diagnostics: &[u8]) -> Error {
+    let diagnostics = String::from_utf8_lossy(diagnostics);
+    let diagnostics = diagnostics.trim_end();
+    if diagnostics.is_empty() {
+        return err;
+    }
+    Error::new(err.kind, format!("{}\n{diagnostics}", err.message))
 }
 
 pub(crate) fn spawn_and_wait_for_output(
     cmd: &mut Command,
     cargo_output: &CargoOutput,
+    kind: CommandKind,
 ) -> Result<Output, Error> {
     // We specifically need the output to be captured, so override default
     let mut captured_cargo_output = cargo_output.clone();
     captured_cargo_output.output = OutputKind::Capture;
-    spawn(cmd, &captured_cargo_output)?
+    spawn(cmd, &captured_cargo_output, kind)?
         .wait_with_output()
         .map_err(|e| {
             Error::new(
@@ -362,17 +491,21 @@ pub(crate) fn spawn_and_wait_for_output(
         })
 }
 
-pub(crate) fn run_output(cmd: &mut Command, cargo_output: &CargoOutput) -> Result<Vec<u8>, Error> {
+pub(crate) fn run_output(
+    cmd: &mut Command,
+    cargo_output: &CargoOutput,
+    kind: CommandKind,
+) -> Result<Vec<u8>, Error> {
     let Output {
         status,
         stdout,
         stderr,
-    } = spawn_and_wait_for_output(cmd, cargo_output)?;
+    } = spawn_and_wait_for_output(cmd, cargo_output, kind)?;
 
     stderr
         .split(|&b| b == b'\n')
         .filter(|part| !part.is_empty())
-        .for_each(write_warning);
+        .for_each(|line| cargo_output.print_compiler_stderr(line));
 
     cargo_output.print_debug(&status);
 
@@ -386,7 +519,40 @@ pub(crate) fn run_output(cmd: &mut Command, cargo_output: &CargoOutput) -> Resul
     }
 }
 
-pub(crate) fn spawn(cmd: &mut Command, cargo_output: &CargoOutput) -> Result<Child, Error> {
+/// Run the probes `cmds` and collect their output, like [`Command::output`].
+///
+/// With the `parallel` feature, they run concurrently, as many at once as the
+/// jobserver allows. Otherwise they run one after another.
+pub(crate) fn probe_outputs(
+    cmds: &mut [Command],
+    executor: &dyn CommandExecutor,
+) -> Vec<Result<Output, Error>> {
+    for cmd in cmds.iter_mut() {
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+    }
+
+    #[cfg(feature = "parallel")]
+    return crate::parallel::outputs_in_parallel(cmds, executor);
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        cmds.iter_mut()
+            .map(|cmd| {
+                Ok(executor
+                    .spawn(cmd, CommandKind::Probe)?
+                    .wait_with_output()?)
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn spawn(
+    cmd: &mut Command,
+    cargo_output: &CargoOutput,
+    kind: CommandKind,
+) -> Result<Child, Error> {
     struct ResetStderr<'cmd>(&'cmd mut Command);
 
     impl Drop for ResetStderr<'_> {
@@ -400,11 +566,10 @@ pub(crate) fn spawn(cmd: &mut Command, cargo_output: &CargoOutput) -> Result<Chi
     cargo_output.print_debug(&format_args!("running: {cmd:?}"));
 
     let cmd = ResetStderr(cmd);
-    let child = cmd
-        .0
+    cmd.0
         .stderr(cargo_output.stdio_for_warnings())
-        .stdout(cargo_output.stdio_for_output())
-        .spawn();
+        .stdout(cargo_output.stdio_for_output());
+    let child = cargo_output.executor().spawn(cmd.0, kind);
     match child {
         Ok(child) => Ok(child),
         Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
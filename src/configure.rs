@@ -0,0 +1,158 @@
+//! Expansion of CMake `configure_file`-style templates.
+
+use std::collections::HashMap;
+
+/// Expand `template` the way CMake's `configure_file` does: `@VAR@` and
+/// `${VAR}` are replaced by the value of `VAR` (or nothing if it is unset),
+/// and `#cmakedefine` / `#cmakedefine01` lines turn into defines.
+pub(crate) fn expand(template: &str, vars: &HashMap<&str, &str>) -> String {
+    let mut out = String::with_capacity(template.len());
+    for line in template.split_inclusive('\n') {
+        let (content, newline) = match line.strip_suffix('\n') {
+            Some(content) => (content, "\n"),
+            None => (line, ""),
+        };
+        match expand_cmakedefine(content, vars) {
+            Some(define) => out.push_str(&define),
+            None => out.push_str(&expand_vars(content, vars)),
+        }
+        out.push_str(newline);
+    }
+    out
+}
+
+/// Expand a `#cmakedefine` or `#cmakedefine01` line, or return `None` if
+/// `line` is not one.
+fn expand_cmakedefine(line: &str, vars: &HashMap<&str, &str>) -> Option<String> {
+    let rest = line.trim_start().strip_prefix('#')?;
+    let spaces = &rest[..rest.len() - rest.trim_start().len()];
+    let rest = rest.trim_start().strip_prefix("cmakedefine")?;
+    let indent = &line[..line.len() - line.trim_start().len()];
+
+    let (is_01, rest) = match rest.strip_prefix("01") {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    if !rest.starts_with(|c: char| c.is_ascii_whitespace()) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let name_len = rest
+        .find(|c: char| c.is_ascii_whitespace())
+        .unwrap_or(rest.len());
+    let (name, value) = rest.split_at(name_len);
+    let enabled = vars.get(name).map_or(false, |value| is_true(value));
+
+    Some(if is_01 {
+        format!("{indent}#{spaces}define {name} {}", enabled as u8)
+    } else if enabled {
+        format!("{indent}#{spaces}define {name}{}", expand_vars(value, vars))
+    } else {
+        format!("{indent}/* #{spaces}undef {name} */")
+    })
+}
+
+/// Replace `@VAR@` and `${VAR}` references in `s`.
+fn expand_vars(s: &str, vars: &HashMap<&str, &str>) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(idx) = rest.find(['@', '$']) {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        let reference = if let Some(after) = rest.strip_prefix('@') {
+            after
+                .find('@')
+                .map(|end| (&after[..end], end + 2))
+                .filter(|(name, _)| is_var_name(name))
+        } else if let Some(after) = rest.strip_prefix("${") {
+            after
+                .find('}')
+                .map(|end| (&after[..end], end + 3))
+                .filter(|(name, _)| is_var_name(name))
+        } else {
+            None
+        };
+
+        match reference {
+            Some((name, len)) => {
+                out.push_str(vars.get(name).copied().unwrap_or(""));
+                rest = &rest[len..];
+            }
+            None => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '+' | '/'))
+}
+
+/// Whether `value` counts as true for `#cmakedefine`, following CMake's
+/// rules for `if(<constant>)`.
+fn is_true(value: &str) -> bool {
+    let upper = value.to_ascii_uppercase();
+    !(matches!(
+        &*upper,
+        "" | "0" | "OFF" | "NO" | "FALSE" | "N" | "IGNORE" | "NOTFOUND"
+    ) || upper.ends_with("-NOTFOUND"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand;
+    use std::collections::HashMap;
+
+    #[test]
+    fn expands_templates() {
+        let vars: HashMap<&str, &str> = [
+            ("VERSION", "1.2.3"),
+            ("HAVE_FOO", "1"),
+            ("HAVE_BAR", "OFF"),
+            ("BAZ", "baz"),
+            ("LIB", "lib-NOTFOUND"),
+        ]
+        .iter()
+        .copied()
+        .collect();
+
+        let template = "\
+#define VERSION \"@VERSION@\"
+#define ALSO_VERSION \"${VERSION}\"
+#define UNSET \"@UNSET@\"
+#cmakedefine HAVE_FOO
+#cmakedefine HAVE_BAR
+#cmakedefine BAZ \"@BAZ@\"
+#  cmakedefine01 HAVE_FOO
+#cmakedefine01 HAVE_BAR
+#cmakedefine01 UNSET
+#cmakedefine01 LIB
+int x = a @ b; /* email@example.com $ {x} */
+#cmakedefinex HAVE_FOO";
+
+        assert_eq!(
+            expand(template, &vars),
+            "\
+#define VERSION \"1.2.3\"
+#define ALSO_VERSION \"1.2.3\"
+#define UNSET \"\"
+#define HAVE_FOO
+/* #undef HAVE_BAR */
+#define BAZ \"baz\"
+#  define HAVE_FOO 1
+#define HAVE_BAR 0
+#define UNSET 0
+#define LIB 0
+int x = a @ b; /* email@example.com $ {x} */
+#cmakedefinex HAVE_FOO"
+        );
+    }
+}
@@ -13,3 +13,95 @@
 #ifdef __VXWORKS__
 #pragma message "VxWorks"
 #endif
+
+#ifdef __GNUC__
+cc_version_gnu __GNUC__ __GNUC_MINOR__ __GNUC_PATCHLEVEL__
+#endif
+
+#ifdef __clang__
+cc_version_clang __clang_major__ __clang_minor__ __clang_patchlevel__
+#endif
+
+#ifdef __LCC__
+cc_version_lcc __LCC__ __LCC_MINOR__
+#endif
+
+#ifdef _MSC_VER
+cc_version_msvc _MSC_VER _MSC_FULL_VER
+#endif
+
+#ifdef __LCC__
+#pragma message "lcc"
+#endif
+
+#ifdef __zig__
+#pragma message "zig"
+#endif
+
+#ifdef __apple_build_version__
+#pragma message "apple"
+#endif
+
+/* The default target. Values are quoted since e.g. `linux` and `i386` may be macros. */
+#if defined(__x86_64__) || defined(_M_X64)
+cc_target_arch "x86_64"
+#elif defined(__i386__) || defined(_M_IX86)
+cc_target_arch "i686"
+#elif defined(__aarch64__) || defined(_M_ARM64)
+cc_target_arch "aarch64"
+#elif defined(__arm__) || defined(_M_ARM)
+cc_target_arch "arm"
+#elif defined(__e2k__)
+cc_target_arch "e2k"
+#elif defined(__riscv) && __riscv_xlen == 64
+cc_target_arch "riscv64"
+#elif defined(__riscv)
+cc_target_arch "riscv32"
+#elif defined(__powerpc64__)
+cc_target_arch "powerpc64"
+#elif defined(__powerpc__)
+cc_target_arch "powerpc"
+#elif defined(__mips__)
+cc_target_arch "mips"
+#elif defined(__s390x__)
+cc_target_arch "s390x"
+#elif defined(__loongarch64)
+cc_target_arch "loongarch64"
+#elif defined(__wasm32__)
+cc_target_arch "wasm32"
+#elif defined(__wasm64__)
+cc_target_arch "wasm64"
+#endif
+
+#if defined(__ANDROID__)
+cc_target_os "android"
+#elif defined(__linux__)
+cc_target_os "linux"
+#elif defined(_WIN32)
+cc_target_os "windows"
+#elif defined(__FreeBSD__)
+cc_target_os "freebsd"
+#elif defined(__NetBSD__)
+cc_target_os "netbsd"
+#elif defined(__OpenBSD__)
+cc_target_os "openbsd"
+#elif defined(__DragonFly__)
+cc_target_os "dragonfly"
+#elif defined(__wasi__)
+cc_target_os "wasi"
+#elif defined(__EMSCRIPTEN__)
+cc_target_os "emscripten"
+#elif defined(__Fuchsia__)
+cc_target_os "fuchsia"
+#elif defined(__HAIKU__)
+cc_target_os "haiku"
+#endif
+
+/* Clang defines `__gnu_linux__` for all Linux targets, GCC only with glibc. */
+#if defined(__MINGW32__)
+cc_target_env "gnu"
+#elif defined(_MSC_VER)
+cc_target_env "msvc"
+#elif defined(__gnu_linux__) && !defined(__clang__)
+cc_target_env "gnu"
+#endif
@@ -0,0 +1,58 @@
+//! Running of the commands that `cc` invokes, see [`CommandExecutor`].
+
+use std::fmt;
+use std::io;
+use std::process::{Child, Command};
+
+/// What a command run by a [`Build`](crate::Build) is for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CommandKind {
+    /// Compiling a source file, or preprocessing it with
+    /// [`Build::expand`](crate::Build::expand).
+    Compile,
+    /// Creating or updating a static library, including the CUDA device link
+    /// step.
+    Archive,
+    /// Querying the compiler, e.g. to detect its family or whether it supports
+    /// a flag.
+    Probe,
+}
+
+/// Starts the processes of a [`Build`](crate::Build).
+///
+/// By default, `cc` spawns every command directly, as implemented by
+/// [`DefaultExecutor`]. A custom executor can be set with
+/// [`Build::executor`](crate::Build::executor), e.g. to run the compiler
+/// through a remote execution service or inside a container, or to retry
+/// and log invocations.
+///
+/// `cmd` is fully configured when passed to [`spawn`](Self::spawn),
+/// including its stdio: `cc` reads the compiler's stdout and stderr from the
+/// returned [`Child`] if they were piped. An executor that runs a different
+/// command instead should pipe them in the same way, as diagnostics and probe
+/// results are lost otherwise.
+///
+/// With the `parallel` feature, a jobserver token is held from before
+/// `spawn` is called until the child has exited, and `spawn` may be called
+/// from multiple threads at once.
+pub trait CommandExecutor: Send + Sync {
+    /// Start `cmd`, which is for the given `kind` of step.
+    fn spawn(&self, cmd: &mut Command, kind: CommandKind) -> io::Result<Child>;
+}
+
+impl fmt::Debug for dyn CommandExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandExecutor").finish_non_exhaustive()
+    }
+}
+
+/// The default [`CommandExecutor`], which spawns every command as is.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultExecutor;
+
+impl CommandExecutor for DefaultExecutor {
+    fn spawn(&self, cmd: &mut Command, _kind: CommandKind) -> io::Result<Child> {
+        cmd.spawn()
+    }
+}
@@ -196,8 +196,7 @@ impl<'this> RustcCodegenFlags<'this> {
             }
         };
 
-        let clang_or_gnu =
-            matches!(family, ToolFamily::Clang { .. }) || matches!(family, ToolFamily::Gnu { .. });
+        let clang_or_gnu = matches!(family, ToolFamily::Clang { .. }) || family.is_gnu();
 
         // Flags shared between clang and gnu
         if clang_or_gnu {
@@ -343,7 +342,7 @@ impl<'this> RustcCodegenFlags<'this> {
                     }
                 }
             }
-            ToolFamily::Gnu { .. } => {}
+            ToolFamily::Gnu | ToolFamily::Lcc => {}
             ToolFamily::Msvc { .. } => {
                 // https://learn.microsoft.com/en-us/cpp/build/reference/guard-enable-control-flow-guard
                 if let Some(value) = self.control_flow_guard {
@@ -0,0 +1,143 @@
+//! A minimal JSON parser, enough for the JSON files that `cc` reads, such as
+//! target specs and Apple's `SDKSettings.json`.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+pub(crate) enum Value {
+    String(String),
+    /// A number, as written in the JSON.
+    Number(String),
+    Object(Vec<(String, Value)>),
+    /// Any other value, which `cc` doesn't need.
+    Other,
+}
+
+impl Value {
+    /// The value of the field `key` of an object. Like most parsers, the
+    /// last one wins if the field is repeated.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn parse(s: &str) -> Result<Value, String> {
+    let mut chars = s.chars().peekable();
+    let value = parse_value(&mut chars)?;
+    skip_whitespace(&mut chars);
+    match chars.next() {
+        None => Ok(value),
+        Some(c) => Err(format!("unexpected `{c}` after the value")),
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Peekable<Chars<'_>>, expected: char) -> Result<(), String> {
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        Some(c) => Err(format!("expected `{expected}`, found `{c}`")),
+        None => Err(format!("expected `{expected}`, found the end")),
+    }
+}
+
+fn parse_value(chars: &mut Peekable<Chars<'_>>) -> Result<Value, String> {
+    skip_whitespace(chars);
+    match chars.peek().copied() {
+        Some('{') => {
+            chars.next();
+            let mut fields = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Ok(Value::Object(fields));
+            }
+            loop {
+                skip_whitespace(chars);
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                expect(chars, ':')?;
+                fields.push((key, parse_value(chars)?));
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some(',') => {}
+                    Some('}') => return Ok(Value::Object(fields)),
+                    _ => return Err("expected `,` or `}` in an object".into()),
+                }
+            }
+        }
+        Some('[') => {
+            chars.next();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Ok(Value::Other);
+            }
+            loop {
+                parse_value(chars)?;
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some(',') => {}
+                    Some(']') => return Ok(Value::Other),
+                    _ => return Err("expected `,` or `]` in an array".into()),
+                }
+            }
+        }
+        Some('"') => parse_string(chars).map(Value::String),
+        Some(c) if c == '-' || c.is_ascii_digit() => {
+            let mut number = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+                number.push(c);
+            }
+            Ok(Value::Number(number))
+        }
+        Some(c) if c.is_ascii_alphabetic() => {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                word.push(c);
+            }
+            match &*word {
+                "null" | "true" | "false" => Ok(Value::Other),
+                _ => Err(format!("unexpected `{word}`")),
+            }
+        }
+        Some(c) => Err(format!("unexpected `{c}`")),
+        None => Err("unexpected end".into()),
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars<'_>>) -> Result<String, String> {
+    expect(chars, '"')?;
+    let mut string = String::new();
+    loop {
+        match chars.next().ok_or("unterminated string")? {
+            '"' => return Ok(string),
+            '\\' => string.push(match chars.next().ok_or("unterminated string")? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let code = u32::from_str_radix(&hex, 16)
+                        .map_err(|_| format!("invalid escape `\\u{hex}`"))?;
+                    // Surrogate pairs are not needed for target specs.
+                    char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                }
+                c => c,
+            }),
+            c => string.push(c),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! The MCST LCC compiler for Elbrus (e2k) targets.
+//!
+//! LCC is driven like GCC and accepts most of its flags, so it shares most of
+//! the GNU handling. The differences are collected here.
+
+use std::ffi::OsStr;
+
+use crate::CompilerVersion;
+
+/// The flag selecting the instruction set for each e2k `full_arch`.
+///
+/// Targets named after an ISA version build for that version with `-march`.
+/// Targets named after a processor only tune for it with `-mtune`, and keep
+/// the ISA version that the compiler was configured for.
+const E2K_ARCH_FLAGS: &[(&str, &str)] = &[
+    ("e2kv3", "-march=elbrus-v3"),
+    ("e2kv4", "-march=elbrus-v4"),
+    ("e2kv5", "-march=elbrus-v5"),
+    ("e2kv6", "-march=elbrus-v6"),
+    ("e2kv7", "-march=elbrus-v7"),
+    ("e2k4c", "-mtune=elbrus-4c"),
+    ("e2k8c", "-mtune=elbrus-8c"),
+    ("e2k1cplus", "-mtune=elbrus-1c+"),
+    ("e2k8c2", "-mtune=elbrus-8c2"),
+    ("e2k12c", "-mtune=elbrus-12c"),
+    ("e2k16c", "-mtune=elbrus-16c"),
+    ("e2k2c3", "-mtune=elbrus-2c3"),
+    ("e2k48c", "-mtune=elbrus-48c"),
+    ("e2k8v7", "-mtune=elbrus-8v7"),
+];
+
+/// The `-march` or `-mtune` flag for an e2k target's `full_arch`, if any.
+pub(crate) fn e2k_arch_flag(full_arch: &str) -> Option<&'static str> {
+    E2K_ARCH_FLAGS
+        .iter()
+        .find(|(arch, _)| *arch == full_arch)
+        .map(|(_, flag)| *flag)
+}
+
+/// LCC's debugger support is built around DWARF 2, which is also what rustc
+/// emits for e2k.
+pub(crate) const DWARF_VERSION: u32 = 2;
+
+/// GCC flags that LCC rejects, e.g. from distribution-provided `CFLAGS`.
+/// Flags taking a value are matched with any value.
+const UNSUPPORTED_FLAGS: &[&str] = &[
+    "-fcf-protection",
+    "-fstack-clash-protection",
+    "-fno-plt",
+    "-fno-semantic-interposition",
+    "-mno-omit-leaf-frame-pointer",
+    "-gsplit-dwarf",
+    "-gz",
+];
+
+/// Whether `flag` is one of [`UNSUPPORTED_FLAGS`], which are dropped when
+/// running LCC.
+pub(crate) fn is_unsupported_flag(flag: &OsStr) -> bool {
+    let flag = match flag.to_str() {
+        Some(flag) => flag,
+        None => return false,
+    };
+    let name = flag.split('=').next().unwrap_or(flag);
+    UNSUPPORTED_FLAGS.contains(&name)
+}
+
+/// Parse the version from the output of `lcc --version`, which starts with
+/// e.g. `lcc:1.25.17:Jun-19-2021:e2k-v4-linux`.
+pub(crate) fn parse_version(stdout: &str) -> Option<CompilerVersion> {
+    let version = stdout
+        .trim_start()
+        .strip_prefix("lcc:")?
+        .split(':')
+        .next()?;
+    let mut numbers = version.split('.').map(|n| n.parse::<u32>().ok());
+    let mut next = || numbers.next().flatten();
+    let major = next()?;
+    Some(CompilerVersion::new(
+        major,
+        next().unwrap_or(0),
+        next().unwrap_or(0),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arch_flags() {
+        assert_eq!(e2k_arch_flag("e2kv4"), Some("-march=elbrus-v4"));
+        assert_eq!(e2k_arch_flag("e2kv7"), Some("-march=elbrus-v7"));
+        assert_eq!(e2k_arch_flag("e2k1cplus"), Some("-mtune=elbrus-1c+"));
+        assert_eq!(e2k_arch_flag("e2k"), None);
+    }
+
+    #[test]
+    fn unsupported_flags() {
+        assert!(is_unsupported_flag("-fcf-protection=full".as_ref()));
+        assert!(is_unsupported_flag("-fno-plt".as_ref()));
+        assert!(!is_unsupported_flag("-fPIC".as_ref()));
+        assert!(!is_unsupported_flag("-fno-plt-foo".as_ref()));
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(
+            parse_version("lcc:1.25.17:Jun-19-2021:e2k-v4-linux\ngcc (GCC) 7.3.0 compatible\n"),
+            Some((1, 25, 17).into())
+        );
+        assert_eq!(
+            parse_version("lcc:1.26:Mar-01-2022:e2k-v5-linux"),
+            Some((1, 26).into())
+        );
+        assert_eq!(parse_version("gcc (GCC) 12.2.0"), None);
+    }
+}
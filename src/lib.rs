@@ -86,6 +86,8 @@
 //! * `CC_ENABLE_DEBUG_OUTPUT` - if set, compiler command invocations and exit codes will
 //!   be logged to stdout. This is useful for debugging build script issues, but can be
 //!   overly verbose for normal use.
+//! * `CC_DETECT_CROSS_SYSROOT` - if set, the sysroot and GCC installation of GNU
+//!   cross toolchains are passed to Clang, see [`Build::detect_cross_sysroot`].
 //! * `CC_SHELL_ESCAPED_FLAGS` - if set, `*FLAGS` will be parsed as if they were shell
 //!   arguments (similar to `make` and `cmake`) rather than splitting them on each space.
 //!   For example, with `CFLAGS='a "b c"'`, the compiler will be invoked with 2 arguments -
@@ -98,6 +100,29 @@
 //!   [`Build::get_compiler`] may produce less accurate results as in some cases `cc` runs
 //!   commands in order to locate compilers. Additionally, this does nothing to prevent
 //!   users from running [`Tool::to_command`] and executing the [`Command`] themselves.
+//! * `CC_PROBE_CACHE_DIR` - if set, the compiler family and the results of
+//!   [`Build::is_flag_supported`] are cached in this directory, so that they can be
+//!   shared between build scripts. If set to an empty value, a directory inside
+//!   Cargo's target directory is used. Entries are keyed on the compiler's path,
+//!   size and modification time as well as its arguments.
+//! * `CC_DRY_RUN` - if set, [`Build::compile`] prints the commands it would run as
+//!   warnings instead of running them, and the Cargo metadata as usual, see
+//!   [`Build::plan`].
+//! * `APPLE_SDKS_DIR` - a list of directories, separated like `PATH`, in which
+//!   to look for Apple SDKs when `xcrun` is not available, e.g. when
+//!   cross-compiling from Linux. A directory may be an SDK itself, contain
+//!   `*.sdk` directories, directly or in `SDK/` as with osxcross, or be an Xcode
+//!   `Developer` directory. The newest SDK for the target is chosen by its
+//!   `SDKSettings.json`, which also provides the default deployment target.
+//! * `ANDROID_NDK_HOME`, `ANDROID_NDK_ROOT`, `ANDROID_HOME` - where to find the
+//!   Android NDK, whose prebuilt Clang and `llvm-ar` are then used for Android
+//!   targets. Under `ANDROID_HOME`, the newest NDK in `ndk/` is chosen.
+//! * `ANDROID_PLATFORM` - the Android API level to build for, e.g. `android-21`,
+//!   if not set with [`Build::android_api_level`].
+//! * `WASI_SDK_PATH` - where to find [wasi-sdk](https://github.com/WebAssembly/wasi-sdk),
+//!   whose Clang, `llvm-ar` and sysroot are then used for WASI targets. If not set,
+//!   it is looked for in common install locations such as `/opt/wasi-sdk`.
+//! * `WASI_SYSROOT` - the WASI sysroot to use instead of the one from wasi-sdk.
 //! * `RUSTC_WRAPPER` - If set, the specified command will be prefixed to the compiler
 //!   command. This is useful for projects that want to use
 //!   [sccache](https://github.com/mozilla/sccache),
@@ -233,6 +258,10 @@
 //! you can use [sccache](https://github.com/mozilla/sccache) by setting
 //! environment variable `RUSTC_WRAPPER=sccache`, which will use cached `.o`
 //! files if the sources are unchanged.
+//!
+//! Alternatively, [`Build::execute_ninja`] generates a build file for
+//! [Ninja](https://ninja-build.org) and runs it, which only recompiles the
+//! sources that changed.
 
 #![doc(html_root_url = "https://docs.rs/cc/1.0")]
 #![deny(warnings)]
@@ -249,7 +278,7 @@ use std::fs;
 use std::io::{self, Write};
 use std::path::{Component, Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 use shlex::Shlex;
 
@@ -258,6 +287,7 @@ mod parallel;
 
 mod target;
 use self::target::*;
+pub use target::{Target, TargetSpec};
 
 /// A helper module to looking for windows-specific tools:
 /// 1. On Windows host, probe the Windows Registry if needed;
@@ -342,10 +372,27 @@ mod command_helpers;
 use command_helpers::*;
 
 mod tool;
-pub use tool::Tool;
-use tool::{CompilerFamilyLookupCache, ToolFamily};
+pub use tool::{CompilerFamily, CompilerVersion, Tool};
+mod executor;
+pub use executor::{CommandExecutor, CommandKind, DefaultExecutor};
+mod output_sink;
+pub use output_sink::{OutputSink, StdoutSink};
+mod android_ndk;
+use android_ndk::AndroidNdk;
+mod apple_sdk;
+use apple_sdk::AppleSdk;
+mod json;
+mod lcc;
+mod ninja;
+mod plan;
+pub use plan::BuildPlan;
+use plan::PlanSink;
+use tool::{CompilerFamilyLookupCache, DetectionContext, ToolFamily, ToolProbeCache};
 
 mod tempfile;
+mod wasi_sdk;
+use wasi_sdk::WasiSdk;
+mod zig;
 
 mod utilities;
 use utilities::*;
@@ -353,18 +400,46 @@ use utilities::*;
 mod flags;
 use flags::*;
 
+mod configure;
+
+mod clang_args;
+
+mod probe_cache;
+use probe_cache::ProbeCache;
+
+pub mod checks;
+
 #[derive(Debug, Eq, PartialEq, Hash)]
 struct CompilerFlag {
     compiler: Box<Path>,
     flag: Box<OsStr>,
 }
 
+#[derive(Debug, Eq, PartialEq, Hash)]
+struct CompilerProbe {
+    compiler: Box<Path>,
+    args: Box<[OsString]>,
+    code: Box<str>,
+}
+
+#[derive(Debug, Eq, PartialEq, Hash)]
+struct CompilerCommand {
+    compiler: Box<Path>,
+    args: Box<[OsString]>,
+}
+
 #[derive(Debug, Default)]
 struct BuildCache {
     apple_sdk_root_cache: RwLock<HashMap<Box<str>, Arc<OsStr>>>,
     apple_versions_cache: RwLock<HashMap<Box<str>, Arc<str>>>,
     cached_compiler_family: RwLock<CompilerFamilyLookupCache>,
     known_flag_support_status_cache: RwLock<HashMap<CompilerFlag, bool>>,
+    known_probe_status_cache: RwLock<HashMap<CompilerProbe, bool>>,
+    compiler_target_triple_cache: RwLock<HashMap<CompilerCommand, Option<Arc<str>>>>,
+    tool_probe_cache: Arc<ToolProbeCache>,
+    /// The on-disk cache, or `None` if `CC_PROBE_CACHE_DIR` hasn't been
+    /// checked yet.
+    probe_cache: RwLock<Option<Option<Arc<ProbeCache>>>>,
     target_info_parser: target::TargetInfoParser,
 }
 
@@ -379,7 +454,8 @@ pub struct Build {
     definitions: Vec<(Arc<str>, Option<Arc<str>>)>,
     objects: Vec<Arc<Path>>,
     flags: Vec<Arc<OsStr>>,
-    flags_supported: Vec<Arc<OsStr>>,
+    /// Groups of alternatives, of which the first supported one is used.
+    flags_supported: Vec<Arc<[Arc<OsStr>]>>,
     ar_flags: Vec<Arc<OsStr>>,
     asm_flags: Vec<Arc<OsStr>>,
     no_default_flags: bool,
@@ -393,6 +469,10 @@ pub struct Build {
     ccbin: bool,
     std: Option<Arc<str>>,
     target: Option<Arc<str>>,
+    target_specs: Vec<(Arc<str>, Arc<TargetSpec>)>,
+    /// The spec of the target, if it is a custom one. Reset when the target
+    /// changes, so it is not in `build_cache`.
+    target_spec: Arc<OnceLock<Result<Option<TargetSpec>, Error>>>,
     /// The host compiler.
     ///
     /// Try to not access this directly, and instead prefer `cfg!(...)`.
@@ -416,10 +496,18 @@ pub struct Build {
     warnings: Option<bool>,
     extra_warnings: Option<bool>,
     emit_rerun_if_env_changed: bool,
+    keep_going: bool,
+    /// Where commands are recorded instead of being run, see `Build::plan`.
+    plan: Option<Arc<Mutex<BuildPlan>>>,
     shell_escaped_flags: Option<bool>,
+    sysroot: Option<Arc<Path>>,
+    detect_cross_sysroot: Option<bool>,
+    use_zig: bool,
+    glibc_version: Option<Arc<str>>,
     build_cache: Arc<BuildCache>,
     inherit_rustflags: bool,
     prefer_clang_cl_over_msvc: bool,
+    android_api_level: Option<u32>,
 }
 
 /// Represents the types of errors that may occur while using cc-rs.
@@ -448,6 +536,10 @@ enum ErrorKind {
     JobserverHelpThreadError,
     /// `cc` has been disabled by an environment variable.
     Disabled,
+    /// The compiler is older than required.
+    UnsupportedCompilerVersion,
+    /// The compiler produces code for a different target than requested.
+    CompilerTargetMismatch,
 }
 
 /// Represents an internal error that occurred, with an explanation.
@@ -527,6 +619,8 @@ impl Build {
             ccbin: true,
             std: None,
             target: None,
+            target_specs: Vec::new(),
+            target_spec: Arc::default(),
             host: None,
             out_dir: None,
             opt_level: None,
@@ -545,10 +639,17 @@ impl Build {
             extra_warnings: None,
             warnings_into_errors: false,
             emit_rerun_if_env_changed: true,
+            keep_going: false,
+            plan: None,
             shell_escaped_flags: None,
+            sysroot: None,
+            detect_cross_sysroot: None,
+            use_zig: false,
+            glibc_version: None,
             build_cache: Arc::default(),
             inherit_rustflags: true,
             prefer_clang_cl_over_msvc: false,
+            android_api_level: None,
         }
     }
 
@@ -601,6 +702,105 @@ impl Build {
         self
     }
 
+    /// Generate a header from a CMake-style `configure_file` template, and
+    /// add the directory containing it to the `-I` include path.
+    ///
+    /// The `input` template is expanded into `output_name` in `OUT_DIR`:
+    /// - `@VAR@` and `${VAR}` are replaced by the value of `VAR` in `vars`,
+    ///   or by nothing if it is not set.
+    /// - `#cmakedefine VAR ...` becomes `#define VAR ...` if `VAR` is set to
+    ///   a true value, and `/* #undef VAR */` otherwise. Like in CMake, empty
+    ///   values and `0`, `OFF`, `NO`, `FALSE`, `N`, `IGNORE`, `NOTFOUND` and
+    ///   `*-NOTFOUND` count as false.
+    /// - `#cmakedefine01 VAR` becomes `#define VAR 1` or `#define VAR 0`.
+    ///
+    /// The output is only rewritten if its contents changed, so that its
+    /// modification time stays stable across build script runs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// cc::Build::new()
+    ///     .configure_file(
+    ///         "vendor/config.h.in",
+    ///         "config.h",
+    ///         [("VERSION", "1.2.3"), ("HAVE_UNISTD_H", "1")],
+    ///     )
+    ///     .file("vendor/foo.c")
+    ///     .compile("foo");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the template could not be read, or the output could not be
+    /// written. See [`try_configure_file`](Self::try_configure_file) for a
+    /// non-panicking version.
+    pub fn configure_file<P, I, K, V>(&mut self, input: P, output_name: &str, vars: I) -> &mut Build
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        if let Err(e) = self.try_configure_file(input, output_name, vars) {
+            fail(&e.message);
+        }
+        self
+    }
+
+    /// Generate a header from a CMake-style `configure_file` template.
+    ///
+    /// This will return a result instead of panicking; see
+    /// [`configure_file()`](Self::configure_file) for the complete
+    /// description. On success, the path to the generated file is returned.
+    pub fn try_configure_file<P, I, K, V>(
+        &mut self,
+        input: P,
+        output_name: &str,
+        vars: I,
+    ) -> Result<PathBuf, Error>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let input = input.as_ref();
+        if output_name.is_empty()
+            || !Path::new(output_name)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("output name `{output_name}` must be a relative path inside `OUT_DIR`"),
+            ));
+        }
+
+        let template = fs::read_to_string(input).map_err(|e| {
+            Error::new(
+                ErrorKind::IOError,
+                format!("failed to read `{}`: {e}", input.display()),
+            )
+        })?;
+        self.cargo_output
+            .print_metadata(&format_args!("cargo:rerun-if-changed={}", input.display()));
+
+        let vars: Vec<(K, V)> = vars.into_iter().collect();
+        let vars = vars
+            .iter()
+            .map(|(k, v)| (k.as_ref(), v.as_ref()))
+            .collect::<HashMap<_, _>>();
+        let contents = configure::expand(&template, &vars);
+
+        let output = self.get_out_dir()?.join(output_name);
+        write_if_changed(&output, contents.as_bytes())?;
+        if let Some(dir) = output.parent() {
+            self.include(dir);
+        }
+        Ok(output)
+    }
+
     /// Specify a `-D` variable with an optional value.
     ///
     /// # Example
@@ -736,7 +936,61 @@ impl Build {
     ///     .compile("foo");
     /// ```
     pub fn flag_if_supported(&mut self, flag: impl AsRef<OsStr>) -> &mut Build {
-        self.flags_supported.push(flag.as_ref().into());
+        self.flags_supported.push(Arc::new([flag.as_ref().into()]));
+        self
+    }
+
+    /// Add each of the given flags to the invocation of the compiler if it
+    /// supports it.
+    ///
+    /// This is like calling [`flag_if_supported`](Self::flag_if_supported)
+    /// for each flag. The checks for all flags are run together when the
+    /// compiler is first configured, concurrently (limited by the jobserver)
+    /// with the `parallel` feature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// cc::Build::new()
+    ///     .file("src/foo.c")
+    ///     .flags_if_supported(["-Wlogical-op", "-Wunreachable-code", "-Wshadow"])
+    ///     .compile("foo");
+    /// ```
+    pub fn flags_if_supported<Iter>(&mut self, flags: Iter) -> &mut Build
+    where
+        Iter: IntoIterator,
+        Iter::Item: AsRef<OsStr>,
+    {
+        for flag in flags {
+            self.flag_if_supported(flag);
+        }
+        self
+    }
+
+    /// Add the first of the given flags that the compiler supports to its
+    /// invocation, if any.
+    ///
+    /// This is useful for picking the newest supported option out of a list
+    /// of fallbacks. All flags are checked together with those from
+    /// [`flag_if_supported`](Self::flag_if_supported).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// cc::Build::new()
+    ///     .file("src/foo.c")
+    ///     .first_supported_flag(["-std=c2x", "-std=c11"])
+    ///     .compile("foo");
+    /// ```
+    pub fn first_supported_flag<Iter>(&mut self, flags: Iter) -> &mut Build
+    where
+        Iter: IntoIterator,
+        Iter::Item: AsRef<OsStr>,
+    {
+        let flags: Arc<[Arc<OsStr>]> = flags.into_iter().map(|f| f.as_ref().into()).collect();
+        if !flags.is_empty() {
+            self.flags_supported.push(flags);
+        }
         self
     }
 
@@ -1129,6 +1383,32 @@ impl Build {
     /// ```
     pub fn target(&mut self, target: &str) -> &mut Build {
         self.target = Some(target.into());
+        self.target_spec = Arc::default();
+        self
+    }
+
+    /// Describes a target that `cc` doesn't know, such as a fork-only or
+    /// in-house target, so that the right `--target` and flags are passed
+    /// when compiling for a target named `name`.
+    ///
+    /// Targets with a target JSON file are found without this, see
+    /// [`TargetSpec`]. A registered spec takes precedence over such a file
+    /// and over `CARGO_CFG_TARGET_*`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let mut spec = cc::TargetSpec::new("aarch64-unknown-none", "aarch64");
+    /// spec.os("acmeos").cpu("cortex-a53");
+    ///
+    /// cc::Build::new()
+    ///     .register_target("aarch64-acme-acmeos", spec)
+    ///     .file("src/foo.c")
+    ///     .compile("foo");
+    /// ```
+    pub fn register_target(&mut self, name: &str, spec: TargetSpec) -> &mut Build {
+        self.target_specs.push((name.into(), spec.into()));
+        self.target_spec = Arc::default();
         self
     }
 
@@ -1281,6 +1561,75 @@ impl Build {
         self
     }
 
+    /// Route all output of `cc` through `sink` instead of printing it to
+    /// stdout.
+    ///
+    /// This includes Cargo metadata, warnings, debug output and everything
+    /// the compiler prints. See [`OutputSink`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Default)]
+    /// struct Collect(Mutex<Vec<String>>);
+    ///
+    /// impl cc::OutputSink for Collect {
+    ///     fn metadata(&self, line: &str) {
+    ///         self.0.lock().unwrap().push(line.to_string());
+    ///     }
+    ///     fn warning(&self, message: &str) {
+    ///         eprintln!("warning: {message}");
+    ///     }
+    ///     fn debug(&self, _message: &str) {}
+    ///     fn compiler_stdout(&self, _output: &[u8]) {}
+    ///     fn compiler_stderr(&self, line: &[u8]) {
+    ///         eprintln!("{}", String::from_utf8_lossy(line));
+    ///     }
+    /// }
+    ///
+    /// let sink = Arc::new(Collect::default());
+    /// cc::Build::new()
+    ///     .file("src/foo.c")
+    ///     .output_sink(sink.clone())
+    ///     .compile("foo");
+    /// ```
+    pub fn output_sink(&mut self, sink: Arc<dyn OutputSink>) -> &mut Build {
+        self.cargo_output.sink = Some(sink);
+        self
+    }
+
+    /// Start the compiler, archiver and compiler probes through `executor`
+    /// instead of spawning them directly.
+    ///
+    /// See [`CommandExecutor`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::io;
+    /// use std::process::{Child, Command};
+    /// use std::sync::Arc;
+    ///
+    /// struct Retry;
+    ///
+    /// impl cc::CommandExecutor for Retry {
+    ///     fn spawn(&self, cmd: &mut Command, _kind: cc::CommandKind) -> io::Result<Child> {
+    ///         cmd.spawn().or_else(|_| cmd.spawn())
+    ///     }
+    /// }
+    ///
+    /// cc::Build::new()
+    ///     .file("src/foo.c")
+    ///     .executor(Arc::new(Retry))
+    ///     .compile("foo");
+    /// ```
+    pub fn executor(&mut self, executor: Arc<dyn CommandExecutor>) -> &mut Build {
+        self.cargo_output.executor = Some(executor);
+        self
+    }
+
     /// Define whether compiler output (to stdout) should be emitted. Defaults to `true`
     /// (forward compiler stdout to this process' stdout)
     ///
@@ -1353,6 +1702,20 @@ impl Build {
         self
     }
 
+    /// Configures whether compilation continues after a source file failed
+    /// to compile.
+    ///
+    /// When enabled, all sources are compiled even if some of them fail, and
+    /// the resulting error lists every failing source together with the
+    /// compiler's diagnostics for it. This is useful when porting code to a
+    /// new target, to see all errors at once.
+    ///
+    /// This option defaults to `false`.
+    pub fn keep_going(&mut self, keep_going: bool) -> &mut Build {
+        self.keep_going = keep_going;
+        self
+    }
+
     /// Configures whether the /MT flag or the /MD flag will be passed to msvc build tools.
     ///
     /// This option defaults to `false`, and affect only msvc targets.
@@ -1370,6 +1733,81 @@ impl Build {
         self
     }
 
+    /// Configure the sysroot, i.e. the directory in which the headers and
+    /// libraries of the target are, as `<sysroot>/usr/include` and so on.
+    ///
+    /// It is passed as `--sysroot` to GCC and Clang, `-isysroot` on Apple
+    /// targets and `/winsysroot` to clang-cl, and has no effect with MSVC. It
+    /// replaces the sysroot that is otherwise found for Apple, WASI, Elbrus
+    /// and other cross targets.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// cc::Build::new()
+    ///     .file("src/foo.c")
+    ///     .sysroot("/usr/aarch64-linux-gnu")
+    ///     .compile("foo");
+    /// ```
+    pub fn sysroot<P: AsRef<Path>>(&mut self, sysroot: P) -> &mut Build {
+        self.sysroot = Some(sysroot.as_ref().into());
+        self
+    }
+
+    /// Configure whether to look for the sysroot and GCC installation of a
+    /// GNU cross toolchain when cross-compiling to Linux with Clang.
+    ///
+    /// Clang does not find them by itself in all layouts, e.g. the one of
+    /// Debian-style multiarch hosts with `/usr/aarch64-linux-gnu` and
+    /// `/usr/lib/gcc-cross/aarch64-linux-gnu`. They are looked for by the
+    /// prefix of the target, e.g. `aarch64-linux-gnu`, in the installation
+    /// root of `<prefix>-gcc` on `PATH`, and in `/usr`. They are passed as
+    /// `--sysroot` and `--gcc-toolchain`.
+    ///
+    /// This option defaults to `false`, unless `CC_DETECT_CROSS_SYSROOT` is
+    /// set. It has no effect if [`sysroot`](Build::sysroot) is set.
+    pub fn detect_cross_sysroot(&mut self, detect_cross_sysroot: bool) -> &mut Build {
+        self.detect_cross_sysroot = Some(detect_cross_sysroot);
+        self
+    }
+
+    /// Configure whether to compile with `zig cc` and `zig c++`, and archive
+    /// with `zig ar`, which can cross-compile to most targets without a
+    /// separate toolchain.
+    ///
+    /// The target is passed to Zig in its own format, e.g.
+    /// `-target aarch64-linux-gnu` for `aarch64-unknown-linux-gnu`. Zig is
+    /// also used if `CC`, `CXX` or [`compiler`](Build::compiler) is `zig`,
+    /// but `CC` and `CXX` take precedence over this option otherwise.
+    ///
+    /// This option defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// cc::Build::new()
+    ///     .file("src/foo.c")
+    ///     .use_zig(true)
+    ///     .glibc_version("2.17")
+    ///     .compile("foo");
+    /// ```
+    pub fn use_zig(&mut self, use_zig: bool) -> &mut Build {
+        self.use_zig = use_zig;
+        self
+    }
+
+    /// Configure the version of glibc to build against on Linux GNU
+    /// targets, e.g. `2.17`, so that the result runs on systems with that
+    /// or a newer version.
+    ///
+    /// This is only supported with [Zig](Build::use_zig), which is passed
+    /// e.g. `-target x86_64-linux-gnu.2.17`. With other compilers, it is
+    /// ignored with a warning.
+    pub fn glibc_version(&mut self, glibc_version: &str) -> &mut Build {
+        self.glibc_version = Some(glibc_version.into());
+        self
+    }
+
     /// Configure whether cc should automatically inherit compatible flags passed to rustc
     /// from `CARGO_ENCODED_RUSTFLAGS`.
     ///
@@ -1387,6 +1825,28 @@ impl Build {
         self
     }
 
+    /// Configure the Android API level to build for, i.e. the lowest
+    /// version of Android that the code will run on, e.g. `21`.
+    ///
+    /// It is passed to Clang as part of the target, e.g.
+    /// `--target=aarch64-linux-android21`. If not set, the API level is read
+    /// from `ANDROID_PLATFORM`, e.g. `android-21`, or else is the lowest that
+    /// the NDK found in `ANDROID_NDK_HOME`, `ANDROID_NDK_ROOT` or
+    /// `ANDROID_HOME/ndk` supports.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// cc::Build::new()
+    ///     .file("src/foo.c")
+    ///     .android_api_level(24)
+    ///     .compile("foo");
+    /// ```
+    pub fn android_api_level(&mut self, android_api_level: u32) -> &mut Build {
+        self.android_api_level = Some(android_api_level);
+        self
+    }
+
     /// Set an environment variable for compiler invocations and other child processes.
     ///
     /// `cc` reads a lot of different variables from the current process' environment. It currently
@@ -1394,6 +1854,7 @@ impl Build {
     /// - `SDKROOT`
     /// - `*_DEPLOYMENT_TARGET`
     /// - `WASI_SDK_ROOT`
+    /// - `ANDROID_NDK_HOME`, `ANDROID_NDK_ROOT`, `ANDROID_HOME` and `ANDROID_PLATFORM`
     ///
     /// The logic here is "environment variables that the C compiler could itself reasonably have
     /// read".
@@ -1439,16 +1900,138 @@ impl Build {
         )
     }
 
-    fn ensure_check_file(&self) -> Result<PathBuf, Error> {
-        let out_dir = self.get_out_dir()?;
-        let src = if self.cuda {
+    /// Compile `code` to test whether the C toolchain supports something,
+    /// and let Rust code know about it via `#[cfg(name)]`.
+    ///
+    /// The snippet is compiled (but not linked) with the fully configured
+    /// compiler, including all flags, defines and include directories set on
+    /// this `Build`. If it compiles, `cargo:rustc-cfg={name}` is emitted.
+    /// `cargo:rustc-check-cfg=cfg({name})` is always emitted, so that the cfg
+    /// is known to rustc either way.
+    ///
+    /// ```no_run
+    /// cc::Build::new().probe_cfg("has_threads_h", "#include <threads.h>");
+    /// ```
+    ///
+    /// Like [`is_flag_supported`](Self::is_flag_supported), the result is
+    /// cached per compiler, so probing the same snippet again is cheap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid cfg name, or if the compiler could not
+    /// be run. See [`try_probe_cfg`](Self::try_probe_cfg) for a non-panicking
+    /// version.
+    pub fn probe_cfg(&self, name: &str, code: &str) -> bool {
+        match self.try_probe_cfg(name, code) {
+            Ok(found) => found,
+            Err(e) => fail(&e.message),
+        }
+    }
+
+    /// Compile `code` and emit `cfg(name)` if it compiled.
+    ///
+    /// This will return a result instead of panicking; see
+    /// [`probe_cfg()`](Self::probe_cfg) for the complete description.
+    pub fn try_probe_cfg(&self, name: &str, code: &str) -> Result<bool, Error> {
+        let is_ident = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_ident {
+            return Err(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("`{name}` is not a valid cfg name"),
+            ));
+        }
+
+        let compiler = self.try_get_compiler()?;
+        let probe = CompilerProbe {
+            compiler: compiler.path().into(),
+            args: compiler.args().iter().cloned().collect(),
+            code: code.into(),
+        };
+
+        let cached = self
+            .build_cache
+            .known_probe_status_cache
+            .read()
+            .unwrap()
+            .get(&probe)
+            .cloned();
+        let found = match cached {
+            Some(found) => found,
+            None => {
+                let found = self.try_compile_check(&format!("probe_{name}"), code, false)?;
+                self.build_cache
+                    .known_probe_status_cache
+                    .write()
+                    .unwrap()
+                    .insert(probe, found);
+                found
+            }
+        };
+
+        self.cargo_output
+            .print_metadata(&format_args!("cargo:rustc-check-cfg=cfg({name})"));
+        if found {
+            self.cargo_output
+                .print_metadata(&format_args!("cargo:rustc-cfg={name}"));
+        }
+
+        Ok(found)
+    }
+
+    /// Fail early if the compiler is part of `family`, but older than `min`.
+    ///
+    /// Compilers of other families are accepted. This is meant for working
+    /// around compiler bugs by rejecting known-broken versions with a clear
+    /// message, instead of failing somewhere in the middle of the build.
+    ///
+    /// ```no_run
+    /// use cc::CompilerFamily;
+    ///
+    /// let build = cc::Build::new();
+    /// build.require_compiler_version(CompilerFamily::Gnu, (8, 1)).unwrap();
+    /// build.require_compiler_version(CompilerFamily::Msvc, (19, 30)).unwrap();
+    /// ```
+    ///
+    /// See [`Tool::version`] for how versions are determined.
+    pub fn require_compiler_version(
+        &self,
+        family: CompilerFamily,
+        min: impl Into<CompilerVersion>,
+    ) -> Result<(), Error> {
+        let min = min.into();
+        let compiler = self.try_get_compiler()?;
+        if compiler.family() != family {
+            return Ok(());
+        }
+
+        let version = compiler.version()?;
+        if version < min {
+            return Err(Error::new(
+                ErrorKind::UnsupportedCompilerVersion,
+                format!(
+                    "{family} {version} (`{}`) is not supported, version {min} or newer is required",
+                    compiler.path().display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_file_extension(&self) -> &'static str {
+        if self.cuda {
             assert!(self.cpp);
-            out_dir.join("flag_check.cu")
+            "cu"
         } else if self.cpp {
-            out_dir.join("flag_check.cpp")
+            "cpp"
         } else {
-            out_dir.join("flag_check.c")
-        };
+            "c"
+        }
+    }
+
+    fn ensure_check_file(&self) -> Result<PathBuf, Error> {
+        let out_dir = self.get_out_dir()?;
+        let src = out_dir.join(format!("flag_check.{}", self.check_file_extension()));
 
         if !src.exists() {
             let mut f = fs::File::create(&src)?;
@@ -1458,34 +2041,174 @@ impl Build {
         Ok(src)
     }
 
+    /// Compile (and optionally link) `code` with the fully configured
+    /// compiler, returning whether the compiler accepted it.
+    ///
+    /// The source and its output are written to `OUT_DIR` under `name`.
+    pub(crate) fn try_compile_check(
+        &self,
+        name: &str,
+        code: &str,
+        link: bool,
+    ) -> Result<bool, Error> {
+        let out_dir = self.get_out_dir()?;
+        let target = self.get_target()?;
+        let compiler = self.try_get_compiler()?;
+
+        let src = out_dir.join(format!("{name}.{}", self.check_file_extension()));
+        fs::write(&src, code)?;
+
+        let mut cmd = compiler.to_command();
+        if link {
+            let exe = out_dir
+                .join(name)
+                .with_extension(env::consts::EXE_EXTENSION);
+            if compiler.is_like_msvc() {
+                let mut s = OsString::from("-Fe");
+                s.push(&exe);
+                cmd.arg(s);
+            } else {
+                cmd.arg("-o").arg(&exe);
+            }
+        } else {
+            command_add_output_file(
+                &mut cmd,
+                &out_dir.join(name).with_extension("o"),
+                CmdAddOutputFileArgs {
+                    cuda: self.cuda,
+                    is_assembler_msvc: false,
+                    msvc: compiler.is_like_msvc(),
+                    clang: compiler.is_like_clang(),
+                    gnu: compiler.is_like_gnu(),
+                    is_asm: false,
+                    is_arm: is_arm(&target),
+                },
+            );
+            cmd.arg("-c");
+        }
+
+        if compiler.supports_path_delimiter() {
+            cmd.arg("--");
+        }
+        cmd.arg(&src);
+
+        let output = spawn_and_wait_for_output(
+            cmd.current_dir(&*out_dir),
+            &self.cargo_output,
+            CommandKind::Probe,
+        )?;
+        self.cargo_output.print_debug(&output.status);
+
+        Ok(output.status.success())
+    }
+
     fn is_flag_supported_inner(
         &self,
         flag: &OsStr,
         tool: &Tool,
         target: &TargetInfo<'_>,
     ) -> Result<bool, Error> {
-        let compiler_flag = CompilerFlag {
-            compiler: tool.path().into(),
-            flag: flag.into(),
-        };
+        Ok(self.are_flags_supported(&[flag], tool, target)?[0])
+    }
 
-        if let Some(is_supported) = self
-            .build_cache
-            .known_flag_support_status_cache
-            .read()
-            .unwrap()
-            .get(&compiler_flag)
-            .cloned()
-        {
-            return Ok(is_supported);
+    /// Check which of `flags` the compiler accepts, running the checks that
+    /// aren't cached concurrently.
+    fn are_flags_supported(
+        &self,
+        flags: &[&OsStr],
+        tool: &Tool,
+        target: &TargetInfo<'_>,
+    ) -> Result<Vec<bool>, Error> {
+        let mut results = vec![false; flags.len()];
+        let mut pending = Vec::new();
+        let mut cmds = Vec::new();
+        // The result may have been stored by another build script.
+        let probe_cache = self.probe_cache();
+
+        for (i, flag) in flags.iter().enumerate() {
+            let compiler_flag = CompilerFlag {
+                compiler: tool.path().into(),
+                flag: (*flag).into(),
+            };
+            if let Some(is_supported) = self
+                .build_cache
+                .known_flag_support_status_cache
+                .read()
+                .unwrap()
+                .get(&compiler_flag)
+                .cloned()
+            {
+                results[i] = is_supported;
+                continue;
+            }
+
+            // Checks running at the same time need their own object file.
+            let obj = match i {
+                0 => "flag_check".to_string(),
+                i => format!("flag_check_{i}"),
+            };
+            let (cmd, disk_key) = self.flag_check_command(flag, tool, target, &obj)?;
+            let cached = match (&probe_cache, &disk_key) {
+                (Some(probe_cache), Some(disk_key)) => {
+                    let disk_key: Vec<&OsStr> = disk_key.iter().map(OsString::as_os_str).collect();
+                    probe_cache.get("flag", &disk_key)
+                }
+                _ => None,
+            };
+            match cached.as_deref() {
+                Some(cached) => {
+                    results[i] = cached == "1";
+                    self.build_cache
+                        .known_flag_support_status_cache
+                        .write()
+                        .unwrap()
+                        .insert(compiler_flag, results[i]);
+                }
+                None => {
+                    pending.push((i, compiler_flag, disk_key));
+                    cmds.push(cmd);
+                }
+            }
+        }
+
+        let outputs = probe_outputs(&mut cmds, self.cargo_output.executor());
+        for ((i, compiler_flag, disk_key), output) in pending.into_iter().zip(outputs) {
+            let output = output?;
+            let is_supported = output.status.success() && output.stderr.is_empty();
+            if let (Some(probe_cache), Some(disk_key)) = (&probe_cache, &disk_key) {
+                let disk_key: Vec<&OsStr> = disk_key.iter().map(OsString::as_os_str).collect();
+                probe_cache.insert("flag", &disk_key, if is_supported { "1" } else { "0" });
+            }
+            self.build_cache
+                .known_flag_support_status_cache
+                .write()
+                .unwrap()
+                .insert(compiler_flag, is_supported);
+            results[i] = is_supported;
         }
 
+        Ok(results)
+    }
+
+    /// Create the command checking whether `tool` accepts `flag`, writing the
+    /// object file `obj` to `OUT_DIR`. Also returns the key to use for the
+    /// on-disk probe cache, if enabled.
+    fn flag_check_command(
+        &self,
+        flag: &OsStr,
+        tool: &Tool,
+        target: &TargetInfo<'_>,
+        obj: &str,
+    ) -> Result<(Command, Option<Vec<OsString>>), Error> {
         let out_dir = self.get_out_dir()?;
         let src = self.ensure_check_file()?;
-        let obj = out_dir.join("flag_check");
-
+        let obj = out_dir.join(obj);
         let mut compiler = {
             let mut cfg = Build::new();
+            // Share the caches, to avoid detecting the compiler family again.
+            cfg.build_cache = self.build_cache.clone();
+            cfg.cargo_output.sink = self.cargo_output.sink.clone();
+            cfg.cargo_output.executor = self.cargo_output.executor.clone();
             cfg.flag(flag)
                 .compiler(tool.path())
                 .cargo_metadata(self.cargo_output.metadata)
@@ -1553,16 +2276,204 @@ impl Build {
             }
         }
 
-        let output = cmd.current_dir(out_dir).output()?;
-        let is_supported = output.status.success() && output.stderr.is_empty();
+        let disk_key = self
+            .probe_cache()
+            .and_then(|_| {
+                let search_path = self.get_env_overridable("PATH");
+                probe_cache::compiler_identity(compiler.path(), search_path.as_deref())
+            })
+            .map(|identity| {
+                [
+                    OsString::from(identity),
+                    OsString::from(self.check_file_extension()),
+                ]
+                .iter()
+                .cloned()
+                .chain(compiler.args().iter().cloned())
+                .collect()
+            });
+        cmd.current_dir(out_dir);
 
-        self.build_cache
-            .known_flag_support_status_cache
-            .write()
-            .unwrap()
-            .insert(compiler_flag, is_supported);
+        Ok((cmd, disk_key))
+    }
 
-        Ok(is_supported)
+    /// Determine the commands that [`compile`](Self::compile) would run to
+    /// generate the file `output`, and the Cargo metadata it would print,
+    /// without running them.
+    ///
+    /// The compiler is still run to detect its family and to check for
+    /// supported flags, so those probes still write their files, e.g. to
+    /// `OUT_DIR` and to the probe cache if it is enabled, but nothing is
+    /// compiled.
+    ///
+    /// The same can be achieved for an existing build script by setting the
+    /// `CC_DRY_RUN` environment variable, in which case [`compile`](Self::compile)
+    /// prints the commands as warnings instead of running them, along with
+    /// the Cargo metadata.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let plan = cc::Build::new().file("src/foo.c").plan("foo");
+    /// for cmd in plan.commands() {
+    ///     println!("{cmd:?}");
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`compile`](Self::compile). See
+    /// [`try_plan`](Self::try_plan) for a non-panicking version.
+    pub fn plan(&self, output: &str) -> BuildPlan {
+        match self.try_plan(output) {
+            Ok(plan) => plan,
+            Err(e) => fail(&e.message),
+        }
+    }
+
+    /// Determine the commands that [`compile`](Self::compile) would run.
+    ///
+    /// This will return a result instead of panicking; see
+    /// [`plan`](Self::plan) for the complete description.
+    pub fn try_plan(&self, output: &str) -> Result<BuildPlan, Error> {
+        let plan = Arc::new(Mutex::new(BuildPlan::default()));
+        let mut build = self.clone();
+        build.plan = Some(plan.clone());
+        build.cargo_output.sink = Some(Arc::new(PlanSink {
+            plan: plan.clone(),
+            inner: self.cargo_output.sink.clone(),
+        }));
+        build.try_compile(output)?;
+
+        let plan = std::mem::take(&mut *plan.lock().unwrap());
+        Ok(plan)
+    }
+
+    /// Write a [Ninja](https://ninja-build.org) build file to `path` that
+    /// builds the static library `output` like [`compile`](Self::compile)
+    /// would, without building it.
+    ///
+    /// The build file has an edge for each object file and one for the
+    /// library, using the commands from [`plan`](Self::plan). The compile
+    /// commands additionally make the compiler report the headers they
+    /// include, so that Ninja only rebuilds what changed, in parallel.
+    ///
+    /// The commands contain paths relative to the current directory, so Ninja
+    /// must be run from there, e.g. with [`execute_ninja`](Self::execute_ninja).
+    /// Unlike `compile`, this doesn't print any Cargo metadata.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let out_dir = std::env::var("OUT_DIR").unwrap();
+    /// cc::Build::new()
+    ///     .files(["src/foo.c", "src/bar.c"])
+    ///     .write_ninja(format!("{out_dir}/build.ninja"), "foo");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`plan`](Self::plan), or if the file can't
+    /// be written. See [`try_write_ninja`](Self::try_write_ninja) for a
+    /// non-panicking version.
+    pub fn write_ninja<P: AsRef<Path>>(&self, path: P, output: &str) {
+        if let Err(e) = self.try_write_ninja(path, output) {
+            fail(&e.message);
+        }
+    }
+
+    /// Write a Ninja build file for the static library `output`.
+    ///
+    /// This will return a result instead of panicking; see
+    /// [`write_ninja`](Self::write_ninja) for the complete description.
+    pub fn try_write_ninja<P: AsRef<Path>>(&self, path: P, output: &str) -> Result<(), Error> {
+        self.write_ninja_inner(path.as_ref(), output).map(|_| ())
+    }
+
+    /// Write a Ninja build file like [`write_ninja`](Self::write_ninja), and
+    /// run `ninja` from `PATH` on it.
+    ///
+    /// Together, this works like [`compile`](Self::compile), including the
+    /// Cargo metadata it prints, except that only the files that changed are
+    /// recompiled when the build script runs again.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`compile`](Self::compile), or if `ninja`
+    /// can't be found or fails. See [`try_execute_ninja`](Self::try_execute_ninja)
+    /// for a non-panicking version.
+    pub fn execute_ninja<P: AsRef<Path>>(&self, path: P, output: &str) {
+        if let Err(e) = self.try_execute_ninja(path, output) {
+            fail(&e.message);
+        }
+    }
+
+    /// Write a Ninja build file for the static library `output`, and run it.
+    ///
+    /// This will return a result instead of panicking; see
+    /// [`execute_ninja`](Self::execute_ninja) for the complete description.
+    pub fn try_execute_ninja<P: AsRef<Path>>(&self, path: P, output: &str) -> Result<(), Error> {
+        let path = path.as_ref();
+        let plan = self.write_ninja_inner(path, output)?;
+
+        let ninja = self
+            .which(Path::new("ninja"), None)
+            .ok_or_else(|| Error::new(ErrorKind::ToolNotFound, "failed to find `ninja` in PATH"))?;
+        run(
+            self.cmd(ninja).arg("-f").arg(path),
+            &self.cargo_output,
+            CommandKind::Compile,
+        )?;
+
+        if self.get_target()?.env == "msvc" && !plan.archive_commands.is_empty() {
+            let lib_name = plan.library.file_stem().unwrap().to_string_lossy();
+            link_msvc_lib(&lib_name[3..], &plan.library)?;
+        }
+        for line in plan.metadata() {
+            self.cargo_output.print_metadata(line);
+        }
+        Ok(())
+    }
+
+    fn write_ninja_inner(&self, path: &Path, output: &str) -> Result<BuildPlan, Error> {
+        let plan = self.try_plan(output)?;
+        let target = self.get_target()?;
+        let compiler = self.try_get_compiler()?;
+        let deps = |obj: &Object| {
+            let is_assembler_msvc =
+                target.env == "msvc" && AsmFileExt::from_path(&obj.src) == Some(AsmFileExt::DotAsm);
+            if is_assembler_msvc {
+                ninja::Deps::None
+            } else if compiler.is_like_msvc() {
+                ninja::Deps::Msvc
+            } else {
+                ninja::Deps::Gcc
+            }
+        };
+        let extra_objects: Vec<&Path> = self.objects.iter().map(|obj| &**obj).collect();
+        fs::write(path, ninja::build_file(&plan, &extra_objects, &deps)).map_err(|e| {
+            Error::new(
+                ErrorKind::IOError,
+                format!("failed to write {}: {e}", path.display()),
+            )
+        })?;
+        Ok(plan)
+    }
+
+    /// Run `cmd`, or record it in the plan if this is a dry run.
+    fn run_or_plan(
+        &self,
+        cmd: &mut Command,
+        step: fn(&mut BuildPlan) -> &mut Vec<Command>,
+    ) -> Result<(), Error> {
+        match &self.plan {
+            Some(plan) => {
+                let cmd = std::mem::replace(cmd, Command::new(""));
+                step(&mut plan.lock().unwrap()).push(cmd);
+                Ok(())
+            }
+            None => run(cmd, &self.cargo_output, CommandKind::Archive),
+        }
     }
 
     /// Run the compiler, generating the file `output`
@@ -1590,9 +2501,29 @@ impl Build {
             gnu.push_str(".a");
             (output, gnu)
         };
+        if self.plan.is_none() && self.get_env_boolean("CC_DRY_RUN") {
+            let plan = self.try_plan(output)?;
+            for cmd in plan.commands() {
+                self.cargo_output
+                    .print_warning(&format_args!("CC_DRY_RUN: not running {cmd:?}"));
+            }
+            for line in plan.metadata() {
+                self.cargo_output.print_metadata(line);
+            }
+            return Ok(());
+        }
+
+        if self.glibc_version.is_some() && self.zig_path().is_none() {
+            self.cargo_output
+                .print_warning(&"glibc_version is specified, but only supported with Zig, ignored");
+        }
+
         let dst = self.get_out_dir()?;
 
         let objects = objects_from_files(&self.files, &dst)?;
+        if let Some(plan) = &self.plan {
+            plan.lock().unwrap().library = dst.join(&gnu_lib_name);
+        }
 
         self.compile_objects(&objects)?;
         self.assemble(lib_name, &dst.join(gnu_lib_name), &objects)?;
@@ -1655,9 +2586,8 @@ impl Build {
                 if target.os == "wasi" {
                     if let Ok(wasi_sysroot) = self.wasi_sysroot() {
                         self.cargo_output.print_metadata(&format_args!(
-                            "cargo:rustc-flags=-L {}/lib/{} -lstatic=c++ -lstatic=c++abi",
-                            Path::new(&wasi_sysroot).display(),
-                            self.get_raw_target()?
+                            "cargo:rustc-flags=-L {} -lstatic=c++ -lstatic=c++abi",
+                            wasi_sdk::lib_dir(&wasi_sysroot, &self.get_raw_target()?).display(),
                         ));
                     }
                 } else if target.os == "linux" {
@@ -1803,22 +2733,138 @@ impl Build {
             ));
         }
 
+        if let Some(plan) = &self.plan {
+            for obj in objs {
+                let cmd = self.create_compile_object_cmd(obj)?;
+                let mut plan = plan.lock().unwrap();
+                plan.compile_commands.push(cmd);
+                plan.objects.push(obj.clone());
+            }
+            return Ok(());
+        }
+
+        if !objs.is_empty() {
+            self.check_compiler_target()?;
+        }
+
         #[cfg(feature = "parallel")]
         if objs.len() > 1 {
-            return parallel::run_commands_in_parallel(
+            let failures = parallel::run_commands_in_parallel(
                 &self.cargo_output,
                 &mut objs.iter().map(|obj| self.create_compile_object_cmd(obj)),
-            );
+                self.keep_going,
+            )?;
+            return keep_going_result(objs, failures);
+        }
+
+        if self.keep_going {
+            let failures = objs
+                .iter()
+                .enumerate()
+                .filter_map(|(i, obj)| {
+                    self.create_compile_object_cmd(obj)
+                        .and_then(|mut cmd| {
+                            run_with_diagnostics(&mut cmd, &self.cargo_output, CommandKind::Compile)
+                        })
+                        .err()
+                        .map(|err| (i, err))
+                })
+                .collect();
+            return keep_going_result(objs, failures);
         }
 
         for obj in objs {
             let mut cmd = self.create_compile_object_cmd(obj)?;
-            run(&mut cmd, &self.cargo_output)?;
+            run(&mut cmd, &self.cargo_output, CommandKind::Compile)?;
         }
 
         Ok(())
     }
 
+    /// Check that the target triple reported by the compiler matches the
+    /// target we're building for, to catch e.g. `CC` pointing to a native
+    /// compiler while cross-compiling.
+    ///
+    /// An architecture mismatch is an error, while a different OS or
+    /// environment only produces a warning, since those may be intentional.
+    fn check_compiler_target(&self) -> Result<(), Error> {
+        if self.cuda {
+            return Ok(());
+        }
+        let compiler = self.try_get_compiler()?;
+        if compiler.family == (ToolFamily::Msvc { clang_cl: false }) {
+            return Ok(());
+        }
+
+        let command = CompilerCommand {
+            compiler: compiler.path().into(),
+            args: compiler.args().iter().cloned().collect(),
+        };
+        let cached = self
+            .build_cache
+            .compiler_target_triple_cache
+            .read()
+            .unwrap()
+            .get(&command)
+            .cloned();
+        // GCC can't switch targets with flags, so the target found while
+        // detecting its family can be used as long as it is complete.
+        let probed = compiler
+            .default_target
+            .clone()
+            .filter(|triple| compiler.family.is_gnu() && triple.split('-').count() == 3);
+        let triple = match cached.or_else(|| probed.map(Some)) {
+            Some(triple) => triple,
+            None => {
+                let triple = match compiler.target_triple() {
+                    Ok(triple) => Some(Arc::from(triple)),
+                    Err(e) => {
+                        self.cargo_output.print_debug(&format_args!(
+                            "failed to query the compiler's target triple: {e}"
+                        ));
+                        None
+                    }
+                };
+                self.build_cache
+                    .compiler_target_triple_cache
+                    .write()
+                    .unwrap()
+                    .insert(command, triple.clone());
+                triple
+            }
+        };
+        let triple = match triple {
+            Some(triple) => triple,
+            None => return Ok(()),
+        };
+
+        let target = self.get_target()?;
+        let mismatch = match target.compiler_triple_mismatch(&triple) {
+            // `musl-gcc` is the system GCC with a specs file for musl, so it
+            // reports the GNU environment.
+            Some(TripleMismatch::Env)
+                if compiler.path().file_stem() == Some(OsStr::new("musl-gcc")) =>
+            {
+                return Ok(())
+            }
+            Some(mismatch) => mismatch,
+            None => return Ok(()),
+        };
+        let message = format!(
+            "{}={} produces {triple} objects but target is {}",
+            if self.cpp { "CXX" } else { "CC" },
+            compiler.path().display(),
+            self.get_raw_target()?,
+        );
+        match mismatch {
+            TripleMismatch::Arch => Err(Error::new(ErrorKind::CompilerTargetMismatch, message)),
+            TripleMismatch::Os | TripleMismatch::Env => {
+                self.cargo_output.print_warning(&message);
+                Ok(())
+            }
+        }
+    }
+
     fn create_compile_object_cmd(&self, obj: &Object) -> Result<Command, Error> {
         let asm_ext = AsmFileExt::from_path(&obj.src);
         let is_asm = asm_ext.is_some();
@@ -1902,7 +2948,7 @@ impl Build {
 
         cmd.args(self.files.iter().map(std::ops::Deref::deref));
 
-        run_output(&mut cmd, &self.cargo_output)
+        run_output(&mut cmd, &self.cargo_output, CommandKind::Compile)
     }
 
     /// Run the compiler, returning the macro-expanded version of the input files.
@@ -1941,14 +2987,81 @@ impl Build {
     ///
     /// # Panics
     ///
-    /// Panics if an error occurred while determining the architecture.
-    pub fn get_compiler(&self) -> Tool {
-        match self.try_get_compiler() {
-            Ok(tool) => tool,
+    /// Panics if an error occurred while determining the architecture.
+    pub fn get_compiler(&self) -> Tool {
+        match self.try_get_compiler() {
+            Ok(tool) => tool,
+            Err(e) => fail(&e.message),
+        }
+    }
+
+    /// Get the arguments to pass to Clang (or `libclang`) to parse headers
+    /// the same way the configured compiler would.
+    ///
+    /// This is meant to keep e.g. bindgen consistent with the C code compiled
+    /// by `cc`. It contains the target triple, the sysroot, Apple SDK and
+    /// deployment target flags, the C/C++ standard, and all include
+    /// directories and defines, from both the defaults and the user
+    /// configuration (including `CFLAGS` and friends).
+    ///
+    /// MSVC-style arguments such as `/I`, `/D` and `/std:` are converted to
+    /// their Clang driver form, and flags that only affect code generation
+    /// (optimization, debug info, warnings, etc.) are left out.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let mut build = cc::Build::new();
+    /// build.include("vendor/include").define("FOO", "1");
+    ///
+    /// let clang_args = build.clang_args();
+    /// // bindgen::builder().header("wrapper.h").clang_args(clang_args)
+    /// # let _ = clang_args;
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an error occurred while determining the compiler or the
+    /// target; see [`try_clang_args`](Self::try_clang_args) for a
+    /// non-panicking version.
+    pub fn clang_args(&self) -> Vec<String> {
+        match self.try_clang_args() {
+            Ok(args) => args,
             Err(e) => fail(&e.message),
         }
     }
 
+    /// Get the arguments to pass to Clang to parse headers the same way the
+    /// configured compiler would.
+    ///
+    /// This will return a result instead of panicking; see
+    /// [`clang_args()`](Self::clang_args) for the complete description.
+    pub fn try_clang_args(&self) -> Result<Vec<String>, Error> {
+        let compiler = self.try_get_compiler()?;
+        let mut args = clang_args::translate(compiler.args(), compiler.is_like_msvc());
+        // Zig's target is not an LLVM one, so it is replaced below.
+        if zig::is_zig(compiler.path()) {
+            if let Some(i) = args.iter().position(|arg| arg == "-target") {
+                args.drain(i..(i + 2).min(args.len()));
+            }
+        }
+
+        if !args
+            .iter()
+            .any(|arg| arg == "-target" || arg.starts_with("--target="))
+        {
+            let target = self.get_target()?;
+            let llvm_target = self.get_llvm_target(&target, None)?;
+            args.insert(0, format!("--target={llvm_target}"));
+        }
+        if self.cpp && !args.iter().any(|arg| arg == "-x") {
+            args.push("-x".to_string());
+            args.push("c++".to_string());
+        }
+
+        Ok(args)
+    }
+
     /// Get the compiler that's in use for this configuration.
     ///
     /// This will return a result instead of panicking; see
@@ -1995,7 +3108,7 @@ impl Build {
         if let Some(ref std) = self.std {
             let separator = match cmd.family {
                 ToolFamily::Msvc { .. } => ':',
-                ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => '=',
+                ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => '=',
             };
             cmd.push_cc_arg(format!("-std{separator}{std}").into());
         }
@@ -2045,12 +3158,25 @@ impl Build {
         for flag in self.flags.iter() {
             cmd.args.push((**flag).into());
         }
-        for flag in self.flags_supported.iter() {
-            if self
-                .is_flag_supported_inner(flag, &cmd, &target)
-                .unwrap_or(false)
-            {
-                cmd.push_cc_arg((**flag).into());
+        if !self.flags_supported.is_empty() {
+            let flags: Vec<&OsStr> = self
+                .flags_supported
+                .iter()
+                .flat_map(|group| group.iter().map(|flag| &**flag))
+                .collect();
+            let mut supported = self
+                .are_flags_supported(&flags, &cmd, &target)
+                .unwrap_or_else(|_| vec![false; flags.len()])
+                .into_iter();
+            for group in self.flags_supported.iter() {
+                let results: Vec<bool> = supported.by_ref().take(group.len()).collect();
+                let first = group
+                    .iter()
+                    .zip(results)
+                    .find_map(|(flag, is_supported)| is_supported.then_some(flag));
+                if let Some(flag) = first {
+                    cmd.push_cc_arg((**flag).into());
+                }
             }
         }
         for (key, value) in self.definitions.iter() {
@@ -2075,6 +3201,9 @@ impl Build {
             cmd.env.push((key.into(), val.into()));
         }
 
+        cmd.probe_cache = Some(self.build_cache.tool_probe_cache.clone());
+        cmd.cargo_output = Some(self.cargo_output.clone());
+
         Ok(cmd)
     }
 
@@ -2114,7 +3243,7 @@ impl Build {
                     _ => {}
                 }
             }
-            ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => {
+            ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => {
                 // arm-linux-androideabi-gcc 4.8 shipped with Android NDK does
                 // not support '-Oz'
                 if opt_level == "z" && !cmd.is_like_clang() {
@@ -2171,14 +3300,24 @@ impl Build {
                     cmd.push_cc_arg("-fno-exceptions".into());
                 }
 
-                if target.os == "wasi" {
-                    // Link clang sysroot
-                    if let Ok(wasi_sysroot) = self.wasi_sysroot() {
-                        cmd.push_cc_arg(
-                            format!("--sysroot={}", Path::new(&wasi_sysroot).display()).into(),
-                        );
+                // Link clang sysroot. Zig has its own copy of wasi-libc.
+                if target.os == "wasi" && (self.sysroot.is_some() || !zig::is_zig(&cmd.path)) {
+                    match self.wasi_sysroot() {
+                        Ok(wasi_sysroot) => {
+                            cmd.push_cc_arg(format!("--sysroot={}", wasi_sysroot.display()).into())
+                        }
+                        // Clang may still have a default sysroot, e.g. from a
+                        // configuration file, so this is not fatal.
+                        Err(e) => {
+                            // Each line separately, as `cargo:warning=` is single-line.
+                            for line in e.message.lines() {
+                                self.cargo_output.print_warning(&line);
+                            }
+                        }
                     }
+                }
 
+                if target.os == "wasi" {
                     // FIXME(madsmtm): Read from `target_features` instead?
                     if raw_target.contains("threads") {
                         cmd.push_cc_arg("-pthread".into());
@@ -2241,10 +3380,30 @@ impl Build {
             }
         }
 
+        if !cmd.is_like_msvc() {
+            // The LLVM triple of a custom target doesn't imply its CPU and
+            // features, so pass them with any compiler.
+            if let Some(spec) = self.get_target_spec()? {
+                let flags = spec.defaults().flags(target);
+                cmd.args.extend(flags.into_iter().map(OsString::from));
+            }
+        }
+
         // Target flags
         match cmd.family {
             ToolFamily::Clang { .. } => {
-                if !(cmd.has_internal_target_arg
+                if zig::is_zig(&cmd.path) {
+                    // Zig has its own names for targets, and rejects LLVM's.
+                    let apple_deployment_target =
+                        (target.vendor == "apple").then(|| self.apple_deployment_target(target));
+                    let zig_target = zig::target(
+                        target,
+                        self.glibc_version.as_deref(),
+                        apple_deployment_target.as_deref(),
+                    )?;
+                    cmd.push_cc_arg("-target".into());
+                    cmd.push_cc_arg(zig_target.into());
+                } else if !(cmd.has_internal_target_arg
                     || (target.os == "android"
                         && android_clang_compiler_uses_target_arg_internally(&cmd.path)))
                 {
@@ -2316,37 +3475,33 @@ impl Build {
                         None
                     };
 
-                    let clang_target =
-                        target.llvm_target(&self.get_raw_target()?, version.as_deref());
+                    let is_custom = self.get_target_spec()?.is_some();
+                    let android_api_level = if is_custom {
+                        None
+                    } else {
+                        self.get_android_api_level(target)
+                    };
+                    let clang_target = if let Some(api_level) = android_api_level {
+                        // Like the NDK's `<triple><api>-clang` wrappers.
+                        Cow::Owned(android_ndk::clang_target(
+                            &self.get_raw_target()?,
+                            api_level,
+                        ))
+                    } else if let Some(wasi_target) = (target.os == "wasi" && !is_custom)
+                        .then(|| self.wasi_sysroot().ok())
+                        .flatten()
+                        .and_then(|sysroot| {
+                            wasi_sdk::clang_target(&sysroot, &self.get_raw_target().ok()?)
+                        })
+                    {
+                        Cow::Borrowed(wasi_target)
+                    } else {
+                        self.get_llvm_target(target, version.as_deref())?
+                    };
                     cmd.push_cc_arg(format!("--target={clang_target}").into());
-
-                    // Set -march or -mtune for e2k targets.
-                    if target.full_arch == "e2kv3" {
-                        cmd.args.push("-march=elbrus-v3".into());
-                    } else if target.full_arch == "e2kv4" {
-                        cmd.args.push("-march=elbrus-v4".into());
-                    } else if target.full_arch == "e2kv5" {
-                        cmd.args.push("-march=elbrus-v5".into());
-                    } else if target.full_arch == "e2kv6" {
-                        cmd.args.push("-march=elbrus-v6".into());
-                    } else if target.full_arch == "e2k4c" {
-                        cmd.args.push("-mtune=elbrus-4c".into());
-                    } else if target.full_arch == "e2k8c" {
-                        cmd.args.push("-mtune=elbrus-8c".into());
-                    } else if target.full_arch == "e2k1cplus" {
-                        cmd.args.push("-mtune=elbrus-1c+".into());
-                    } else if target.full_arch == "e2k8c2" {
-                        cmd.args.push("-mtune=elbrus-8c2".into());
-                    } else if target.full_arch == "e2k12c" {
-                        cmd.args.push("-mtune=elbrus-12c".into());
-                    } else if target.full_arch == "e2k16c" {
-                        cmd.args.push("-mtune=elbrus-16c".into());
-                    } else if target.full_arch == "e2k2c3" {
-                        cmd.args.push("-mtune=elbrus-2c3".into());
-                    } else if target.full_arch == "e2k48c" {
-                        cmd.args.push("-mtune=elbrus-48c".into());
-                    } else if target.full_arch == "e2k8v7" {
-                        cmd.args.push("-mtune=elbrus-8v7".into());
+                    // The wrappers pass this too, see `try_get_compiler`.
+                    if target.arch == "x86" && android_api_level.map_or(false, |l| l < 25) {
+                        cmd.push_cc_arg("-mstackrealign".into());
                     }
                 }
             }
@@ -2358,11 +3513,7 @@ impl Build {
 
                 if clang_cl {
                     cmd.push_cc_arg(
-                        format!(
-                            "--target={}",
-                            target.llvm_target(&self.get_raw_target()?, None)
-                        )
-                        .into(),
+                        format!("--target={}", self.get_llvm_target(target, None)?).into(),
                     );
 
                     if target.arch == "x86" {
@@ -2396,83 +3547,48 @@ impl Build {
                         .push("-D_ARM_WINAPI_PARTITION_DESKTOP_SDK_AVAILABLE=1".into());
                 }
             }
-            ToolFamily::Gnu { .. } => {
+            ToolFamily::Gnu | ToolFamily::Lcc => {
                 if target.vendor == "kmc" {
                     cmd.args.push("-finput-charset=utf-8".into());
                 }
 
-                if self.static_flag.is_none() {
-                    let features = cargo_env_var_os("CARGO_CFG_TARGET_FEATURE");
-                    let features = features.as_deref().unwrap_or_default();
-                    if features.to_string_lossy().contains("crt-static") {
-                        cmd.args.push("-static".into());
+                if target.arch == "e2k" && self.sysroot.is_none() && self.get_is_cross_compile()? {
+                    if let Some(sysroot) = self.e2k_cross_sysroot(&cmd.path) {
+                        cmd.args
+                            .push(format!("--sysroot={}", sysroot.display()).into());
                     }
                 }
 
-                // armv7 targets get to use armv7 instructions
-                if (target.full_arch.starts_with("armv7")
-                    || target.full_arch.starts_with("thumbv7"))
-                    && (target.os == "linux" || target.vendor == "kmc")
-                {
-                    cmd.args.push("-march=armv7-a".into());
-
-                    if target.abi == "eabihf" {
-                        // lowest common denominator FPU
-                        cmd.args.push("-mfpu=vfpv3-d16".into());
-                        cmd.args.push("-mfloat-abi=hard".into());
+                if cmd.family == ToolFamily::Lcc {
+                    if let Some(flag) = lcc::e2k_arch_flag(target.full_arch) {
+                        cmd.args.push(flag.into());
                     }
                 }
 
-                // (x86 Android doesn't say "eabi")
-                if target.os == "android" && target.full_arch.contains("v7") {
-                    cmd.args.push("-march=armv7-a".into());
-                    cmd.args.push("-mthumb".into());
-                    if !target.full_arch.contains("neon") {
-                        // On android we can guarantee some extra float instructions
-                        // (specified in the android spec online)
-                        // NEON guarantees even more; see below.
-                        cmd.args.push("-mfpu=vfpv3-d16".into());
+                if self.static_flag.is_none() {
+                    let features = cargo_env_var_os("CARGO_CFG_TARGET_FEATURE");
+                    let features = features.as_deref().unwrap_or_default();
+                    if features.to_string_lossy().contains("crt-static") {
+                        cmd.args.push("-static".into());
                     }
-                    cmd.args.push("-mfloat-abi=softfp".into());
                 }
 
-                if target.full_arch.contains("neon") {
-                    cmd.args.push("-mfpu=neon-vfpv4".into());
-                }
-
-                if target.full_arch == "armv4t" && target.os == "linux" {
-                    cmd.args.push("-march=armv4t".into());
-                    cmd.args.push("-marm".into());
-                    cmd.args.push("-mfloat-abi=soft".into());
-                }
-
-                if target.full_arch == "armv5te" && target.os == "linux" {
-                    cmd.args.push("-march=armv5te".into());
-                    cmd.args.push("-marm".into());
-                    cmd.args.push("-mfloat-abi=soft".into());
-                }
-
-                // For us arm == armv6 by default
-                if target.full_arch == "arm" && target.os == "linux" {
-                    cmd.args.push("-march=armv6".into());
-                    cmd.args.push("-marm".into());
-                    if target.abi == "eabihf" {
-                        cmd.args.push("-mfpu=vfp".into());
-                    } else {
-                        cmd.args.push("-mfloat-abi=soft".into());
+                // Generate code for the same baseline as `rustc` does. The
+                // LLVM triple already implies it for Clang, and GCC's names
+                // for the CPUs of other architectures mostly differ from
+                // LLVM's.
+                if matches!(
+                    target.arch,
+                    "x86" | "x86_64" | "arm" | "riscv32" | "riscv64"
+                ) && target.vendor != "apple"
+                    && self.get_target_spec()?.is_none()
+                {
+                    if let Some(defaults) = TargetDefaults::from_rustc_target(&raw_target) {
+                        let flags = defaults.flags(target);
+                        cmd.args.extend(flags.into_iter().map(OsString::from));
                     }
                 }
 
-                // Turn codegen down on i586 to avoid some instructions.
-                if target.full_arch == "i586" && target.os == "linux" {
-                    cmd.args.push("-march=pentium".into());
-                }
-
-                // Set codegen level for i686 correctly
-                if target.full_arch == "i686" && target.os == "linux" {
-                    cmd.args.push("-march=i686".into());
-                }
-
                 // Looks like `musl-gcc` makes it hard for `-m32` to make its way
                 // all the way to the linker, so we need to actually instruct the
                 // linker that we're generating 32-bit executables as well. This'll
@@ -2481,86 +3597,38 @@ impl Build {
                 if target.arch == "x86" && target.env == "musl" {
                     cmd.args.push("-Wl,-melf_i386".into());
                 }
+            }
+        }
 
-                if target.arch == "arm" && target.os == "none" && target.abi == "eabihf" {
-                    cmd.args.push("-mfloat-abi=hard".into())
-                }
-                if target.full_arch.starts_with("thumb") {
-                    cmd.args.push("-mthumb".into());
-                }
-                if target.full_arch.starts_with("thumbv6m") {
-                    cmd.args.push("-march=armv6s-m".into());
-                }
-                if target.full_arch.starts_with("thumbv7em") {
-                    cmd.args.push("-march=armv7e-m".into());
-
-                    if target.abi == "eabihf" {
-                        cmd.args.push("-mfpu=fpv4-sp-d16".into())
-                    }
-                }
-                if target.full_arch.starts_with("thumbv7m") {
-                    cmd.args.push("-march=armv7-m".into());
-                }
-                if target.full_arch.starts_with("thumbv8m.base") {
-                    cmd.args.push("-march=armv8-m.base".into());
-                }
-                if target.full_arch.starts_with("thumbv8m.main") {
-                    cmd.args.push("-march=armv8-m.main".into());
-
-                    if target.abi == "eabihf" {
-                        cmd.args.push("-mfpu=fpv5-sp-d16".into())
-                    }
-                }
-                if target.full_arch.starts_with("armebv7r") | target.full_arch.starts_with("armv7r")
-                {
-                    if target.full_arch.starts_with("armeb") {
-                        cmd.args.push("-mbig-endian".into());
-                    } else {
-                        cmd.args.push("-mlittle-endian".into());
-                    }
-
-                    // ARM mode
-                    cmd.args.push("-marm".into());
-
-                    // R Profile
-                    cmd.args.push("-march=armv7-r".into());
-
-                    if target.abi == "eabihf" {
-                        // lowest common denominator FPU
-                        // (see Cortex-R4 technical reference manual)
-                        cmd.args.push("-mfpu=vfpv3-d16".into())
-                    }
+        // Sysroot
+        if let Some(sysroot) = &self.sysroot {
+            match cmd.family {
+                ToolFamily::Msvc { clang_cl: true } => {
+                    cmd.push_cc_arg("/winsysroot".into());
+                    cmd.push_cc_arg(sysroot.as_os_str().into());
                 }
-                if target.full_arch.starts_with("armv7a") {
-                    cmd.args.push("-march=armv7-a".into());
-
-                    if target.abi == "eabihf" {
-                        // lowest common denominator FPU
-                        cmd.args.push("-mfpu=vfpv3-d16".into());
-                    }
+                // MSVC has no equivalent.
+                ToolFamily::Msvc { clang_cl: false } => {}
+                // These are passed where the sysroot would be found for them,
+                // see `apple_sdk_root`, `wasi_sysroot` and `wasm_musl_sysroot`.
+                _ if target.vendor == "apple"
+                    || target.os == "wasi"
+                    || (target.arch == "wasm32" && target.os == "linux") => {}
+                _ => cmd.push_cc_arg(format!("--sysroot={}", sysroot.display()).into()),
+            }
+        } else if cmd.is_like_clang()
+            && target.os == "linux"
+            && self.get_detect_cross_sysroot()
+            && self.get_is_cross_compile()?
+            && self.get_target_spec()?.is_none()
+        {
+            if let Some(prefix) = self.prefix_for_target(&raw_target) {
+                let (sysroot, gcc_toolchain) = self.gnu_cross_toolchain(&prefix);
+                if let Some(sysroot) = sysroot {
+                    cmd.push_cc_arg(format!("--sysroot={}", sysroot.display()).into());
                 }
-                if target.arch == "riscv32" || target.arch == "riscv64" {
-                    // get the 32i/32imac/32imc/64gc/64imac/... part
-                    let arch = &target.full_arch[5..];
-                    if arch.starts_with("64") {
-                        if matches!(target.os, "linux" | "freebsd" | "netbsd") {
-                            cmd.args.push(("-march=rv64gc").into());
-                            cmd.args.push("-mabi=lp64d".into());
-                        } else {
-                            cmd.args.push(("-march=rv".to_owned() + arch).into());
-                            cmd.args.push("-mabi=lp64".into());
-                        }
-                    } else if arch.starts_with("32") {
-                        if target.os == "linux" {
-                            cmd.args.push(("-march=rv32gc").into());
-                            cmd.args.push("-mabi=ilp32d".into());
-                        } else {
-                            cmd.args.push(("-march=rv".to_owned() + arch).into());
-                            cmd.args.push("-mabi=ilp32".into());
-                        }
-                    } else {
-                        cmd.args.push("-mcmodel=medany".into());
-                    }
+                if let Some(gcc_toolchain) = gcc_toolchain {
+                    cmd.push_cc_arg(format!("--gcc-toolchain={}", gcc_toolchain.display()).into());
                 }
             }
         }
@@ -2590,7 +3658,9 @@ impl Build {
             cmd.args.push("-D_REENTRANT".into());
         }
 
-        if target.vendor == "apple" {
+        // Zig has the deployment target in `-target`, and its own copy of the
+        // SDK's headers.
+        if target.vendor == "apple" && !zig::is_zig(&cmd.path) {
             self.apple_flags(cmd)?;
         }
 
@@ -2604,7 +3674,7 @@ impl Build {
         if self.cpp {
             match (self.cpp_set_stdlib.as_ref(), cmd.family) {
                 (None, _) => {}
-                (Some(stdlib), ToolFamily::Gnu { .. })
+                (Some(stdlib), ToolFamily::Gnu | ToolFamily::Lcc)
                 | (Some(stdlib), ToolFamily::Clang { .. }) => {
                     cmd.push_cc_arg(format!("-stdlib=lib{stdlib}").into());
                 }
@@ -2696,7 +3766,9 @@ impl Build {
     fn assemble(&self, lib_name: &str, dst: &Path, objs: &[Object]) -> Result<(), Error> {
         // Delete the destination if it exists as we want to
         // create on the first iteration instead of appending.
-        let _ = fs::remove_file(dst);
+        if self.plan.is_none() {
+            let _ = fs::remove_file(dst);
+        }
 
         // Add objects to the archive in limited-length batches. This helps keep
         // the length of the command line within a reasonable length to avoid
@@ -2707,6 +3779,7 @@ impl Build {
             .chain(self.objects.iter().map(std::ops::Deref::deref))
             .peekable();
         let mut batch = Vec::new();
+        let mut append = false;
         while objs.peek().is_some() {
             let mut remaining_len = 4000;
             while let Some(path) =
@@ -2715,8 +3788,9 @@ impl Build {
                 batch.push(path);
                 remaining_len = remaining_len.saturating_sub(path.as_os_str().len());
             }
-            self.assemble_progressive(dst, &batch)?;
+            self.assemble_progressive(dst, &batch, append)?;
             batch.clear();
+            append = true;
         }
 
         if self.cuda && self.cuda_file_count() > 0 {
@@ -2727,8 +3801,8 @@ impl Build {
             let dlink = out_dir.join(lib_name.to_owned() + "_dlink.o");
             let mut nvcc = self.get_compiler().to_command();
             nvcc.arg("--device-link").arg("-o").arg(&dlink).arg(dst);
-            run(&mut nvcc, &self.cargo_output)?;
-            self.assemble_progressive(dst, &[dlink.as_path()])?;
+            self.run_or_plan(&mut nvcc, |plan| &mut plan.archive_commands)?;
+            self.assemble_progressive(dst, &[dlink.as_path()], true)?;
         }
 
         let target = self.get_target()?;
@@ -2736,21 +3810,11 @@ impl Build {
             // The Rust compiler will look for libfoo.a and foo.lib, but the
             // MSVC linker will also be passed foo.lib, so be sure that both
             // exist for now.
+            if self.plan.is_some() {
+                return Ok(());
+            }
 
-            let lib_dst = dst.with_file_name(format!("{lib_name}.lib"));
-            let _ = fs::remove_file(&lib_dst);
-            match fs::hard_link(dst, &lib_dst).or_else(|_| {
-                // if hard-link fails, just copy (ignoring the number of bytes written)
-                fs::copy(dst, &lib_dst).map(|_| ())
-            }) {
-                Ok(_) => (),
-                Err(_) => {
-                    return Err(Error::new(
-                        ErrorKind::IOError,
-                        "Could not copy or create a hard-link to the generated lib file.",
-                    ));
-                }
-            };
+            link_msvc_lib(lib_name, dst)?;
         } else {
             // Non-msvc targets (those using `ar`) need a separate step to add
             // the symbol table to archives since our construction command of
@@ -2760,17 +3824,21 @@ impl Build {
             // NOTE: We add `s` even if flags were passed using $ARFLAGS/ar_flag, because `s`
             // here represents a _mode_, not an arbitrary flag. Further discussion of this choice
             // can be seen in https://github.com/rust-lang/cc-rs/pull/763.
-            run(ar.arg("s").arg(dst), &self.cargo_output)?;
+            self.run_or_plan(ar.arg("s").arg(dst), |plan| &mut plan.archive_commands)?;
         }
 
         Ok(())
     }
 
-    fn assemble_progressive(&self, dst: &Path, objs: &[&Path]) -> Result<(), Error> {
+    /// Add `objs` to the archive `dst`, which already exists if `append`.
+    fn assemble_progressive(&self, dst: &Path, objs: &[&Path], append: bool) -> Result<(), Error> {
         let target = self.get_target()?;
 
         let (mut cmd, program, any_flags) = self.try_get_archiver_and_flags()?;
-        if target.env == "msvc" && !program.to_string_lossy().contains("llvm-ar") {
+        if target.env == "msvc"
+            && !program.to_string_lossy().contains("llvm-ar")
+            && !zig::is_zig(&program)
+        {
             // NOTE: -out: here is an I/O flag, and so must be included even if $ARFLAGS/ar_flag is
             // in use. -nologo on the other hand is just a regular flag, and one that we'll skip if
             // the caller has explicitly dictated the flags they want. See
@@ -2783,11 +3851,11 @@ impl Build {
             }
             // If the library file already exists, add the library name
             // as an argument to let lib.exe know we are appending the objs.
-            if dst.exists() {
+            if append {
                 cmd.arg(dst);
             }
             cmd.args(objs);
-            run(&mut cmd, &self.cargo_output)?;
+            self.run_or_plan(&mut cmd, |plan| &mut plan.archive_commands)?;
         } else {
             // Set an environment variable to tell the OSX archiver to ensure
             // that all dates listed in the archive are zero, improving
@@ -2816,7 +3884,9 @@ impl Build {
             // NOTE: We add cq here regardless of whether $ARFLAGS/ar_flag have been used because
             // it dictates the _mode_ ar runs in, which the setter of $ARFLAGS/ar_flag can't
             // dictate. See https://github.com/rust-lang/cc-rs/pull/763 for further discussion.
-            run(cmd.arg("cq").arg(dst).args(objs), &self.cargo_output)?;
+            self.run_or_plan(cmd.arg("cq").arg(dst).args(objs), |plan| {
+                &mut plan.archive_commands
+            })?;
         }
 
         Ok(())
@@ -2850,7 +3920,7 @@ impl Build {
         }
 
         // AppleClang sometimes requires sysroot even on macOS
-        if cmd.is_xctoolchain_clang() || target.os != "macos" {
+        if cmd.is_xctoolchain_clang() || target.os != "macos" || self.sysroot.is_some() {
             self.cargo_output.print_metadata(&format_args!(
                 "Detecting {:?} SDK path for {}",
                 target.os,
@@ -2914,17 +3984,61 @@ impl Build {
         }
     }
 
+    /// The persistent cache for compiler probes, if enabled with
+    /// `CC_PROBE_CACHE_DIR`.
+    ///
+    /// If the variable is set to an empty string, the cache is stored in
+    /// `cc-probe-cache` next to Cargo's `build` directory, i.e. in
+    /// `target/<profile>`.
+    fn probe_cache(&self) -> Option<Arc<ProbeCache>> {
+        if let Some(probe_cache) = &*self.build_cache.probe_cache.read().unwrap() {
+            return probe_cache.clone();
+        }
+
+        let dir = self.get_env("CC_PROBE_CACHE_DIR").and_then(|dir| {
+            if !dir.is_empty() {
+                return Some(PathBuf::from(dir));
+            }
+            let out_dir = self.get_out_dir().ok()?;
+            let build_dir = out_dir
+                .ancestors()
+                .find(|dir| dir.file_name() == Some(OsStr::new("build")))?;
+            Some(build_dir.parent()?.join("cc-probe-cache"))
+        });
+        let probe_cache = dir.map(|dir| Arc::new(ProbeCache::new(dir)));
+        *self.build_cache.probe_cache.write().unwrap() = Some(probe_cache.clone());
+        probe_cache
+    }
+
     fn get_base_compiler(&self) -> Result<Tool, Error> {
         let out_dir = self.get_out_dir().ok();
         let out_dir = out_dir.as_deref();
+        let probe_cache = self.probe_cache();
+        let search_path = probe_cache
+            .as_ref()
+            .and_then(|_| self.get_env_overridable("PATH"));
+        let context = DetectionContext {
+            cached_compiler_family: &self.build_cache.cached_compiler_family,
+            probe_cache: probe_cache.as_deref(),
+            cargo_output: &self.cargo_output,
+            out_dir,
+            search_path: search_path.as_deref(),
+            env: &self.env,
+        };
+
+        // Zig is called as e.g. `zig cc`.
+        let zig_subcommand = if self.cpp { "c++" } else { "cc" };
+        let zig_tool = |path: PathBuf| {
+            let mut t = Tool::with_args(path, vec![zig_subcommand.to_string()], &context);
+            t.cc_wrapper_args.push(zig_subcommand.into());
+            t
+        };
 
         if let Some(c) = &self.compiler {
-            return Ok(Tool::new(
-                (**c).to_owned(),
-                &self.build_cache.cached_compiler_family,
-                &self.cargo_output,
-                out_dir,
-            ));
+            if zig::is_zig(c) {
+                return Ok(zig_tool((**c).to_owned()));
+            }
+            return Ok(Tool::new((**c).to_owned(), &context));
         }
         let target = self.get_target()?;
         let raw_target = self.get_raw_target()?;
@@ -2960,18 +4074,15 @@ impl Build {
 
         let tool_opt: Option<Tool> = self
             .env_tool(env)
-            .map(|(tool, wrapper, args)| {
+            .map(|(tool, wrapper, mut args)| {
+                if args.is_empty() && zig::is_zig(&tool) {
+                    args.push(zig_subcommand.to_string());
+                }
                 // Chop off leading/trailing whitespace to work around
                 // semi-buggy build scripts which are shared in
                 // makefiles/configure scripts (where spaces are far more
                 // lenient)
-                let mut t = Tool::with_args(
-                    tool,
-                    args.clone(),
-                    &self.build_cache.cached_compiler_family,
-                    &self.cargo_output,
-                    out_dir,
-                );
+                let mut t = Tool::with_args(tool, args.clone(), &context);
                 if let Some(cc_wrapper) = wrapper {
                     t.cc_wrapper_path = Some(Path::new(&cc_wrapper).to_owned());
                 }
@@ -2980,6 +4091,15 @@ impl Build {
                 }
                 t
             })
+            .or_else(|| {
+                self.use_zig.then(|| {
+                    let mut t = zig_tool(PathBuf::from("zig"));
+                    if let Some(cc_wrapper) = self.rustc_wrapper_fallback() {
+                        t.cc_wrapper_path = Some(Path::new(&cc_wrapper).to_owned());
+                    }
+                    t
+                })
+            })
             .or_else(|| {
                 if target.os == "emscripten" {
                     let tool = if self.cpp { "em++" } else { "emcc" };
@@ -2993,12 +4113,7 @@ impl Build {
                         t.args.push(format!("{tool}.bat").into());
                         Some(t)
                     } else {
-                        Some(Tool::new(
-                            PathBuf::from(tool),
-                            &self.build_cache.cached_compiler_family,
-                            &self.cargo_output,
-                            out_dir,
-                        ))
+                        Some(Tool::new(PathBuf::from(tool), &context))
                     }
                 } else {
                     None
@@ -3023,7 +4138,8 @@ impl Build {
                 {
                     clang.into()
                 } else if target.os == "android" {
-                    autodetect_android_compiler(&raw_target, gnu, clang)
+                    self.android_ndk_tool(clang)
+                        .unwrap_or_else(|| autodetect_android_compiler(&raw_target, gnu, clang))
                 } else if target.os == "cloudabi" {
                     format!(
                         "{}-{}-{}-{}",
@@ -3031,7 +4147,7 @@ impl Build {
                     )
                     .into()
                 } else if target.os == "wasi" {
-                    self.autodetect_wasi_compiler(&raw_target, clang)
+                    self.autodetect_wasi_compiler(&target, &raw_target, clang)
                 } else if target.arch == "wasm32" || target.arch == "wasm64" {
                     // Compiling WASM is not currently supported by GCC, so
                     // let's default to Clang.
@@ -3058,12 +4174,7 @@ impl Build {
                     default.into()
                 };
 
-                let mut t = Tool::new(
-                    compiler,
-                    &self.build_cache.cached_compiler_family,
-                    &self.cargo_output,
-                    out_dir,
-                );
+                let mut t = Tool::new(compiler, &context);
                 if let Some(cc_wrapper) = self.rustc_wrapper_fallback() {
                     t.cc_wrapper_path = Some(Path::new(&cc_wrapper).to_owned());
                 }
@@ -3080,14 +4191,7 @@ impl Build {
                 Err(_) => PathBuf::from("nvcc"),
                 Ok(nvcc) => PathBuf::from(&*nvcc),
             };
-            let mut nvcc_tool = Tool::with_features(
-                nvcc,
-                vec![],
-                self.cuda,
-                &self.build_cache.cached_compiler_family,
-                &self.cargo_output,
-                out_dir,
-            );
+            let mut nvcc_tool = Tool::with_features(nvcc, vec![], self.cuda, &context);
             if self.ccbin {
                 nvcc_tool
                     .args
@@ -3393,6 +4497,71 @@ impl Build {
         Ok(cmd)
     }
 
+    /// Get information about the target of this configuration, as parsed by
+    /// `cc` from [`target`](Self::target) or the `TARGET` and
+    /// `CARGO_CFG_TARGET_*` environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let target = cc::Build::new().get_target_info();
+    /// if target.os() == "linux" && target.full_arch() == "armv7" {
+    ///     println!("cargo:rustc-cfg=linux_armv7");
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target or host can't be determined; see
+    /// [`try_get_target_info`](Self::try_get_target_info) for a non-panicking
+    /// version.
+    pub fn get_target_info(&self) -> Target {
+        match self.try_get_target_info() {
+            Ok(target) => target,
+            Err(e) => fail(&e.message),
+        }
+    }
+
+    /// Get information about the target of this configuration.
+    ///
+    /// This will return a result instead of panicking; see
+    /// [`get_target_info()`](Self::get_target_info) for the complete
+    /// description.
+    pub fn try_get_target_info(&self) -> Result<Target, Error> {
+        let target = self.get_target()?;
+        let is_apple = target.vendor == "apple";
+        let apple_deployment_target = is_apple.then(|| self.apple_deployment_target(&target));
+        // The version is part of the triple where there is no
+        // `-m*-version-min=`, see `add_default_flags`.
+        let version = if target.os == "visionos" || target.env == "macabi" {
+            apple_deployment_target.as_deref()
+        } else {
+            None
+        };
+        let defaults = match self.get_target_spec()? {
+            Some(spec) => Some(spec.defaults()),
+            None => TargetDefaults::from_rustc_target(&self.get_raw_target()?),
+        };
+        Ok(Target {
+            full_arch: target.full_arch.into(),
+            arch: target.arch.into(),
+            vendor: target.vendor.into(),
+            os: target.os.into(),
+            env: target.env.into(),
+            abi: target.abi.into(),
+            llvm_target: self.get_llvm_target(&target, version)?.into(),
+            apple_sdk_name: is_apple.then(|| target.apple_sdk_name()),
+            apple_deployment_target: apple_deployment_target.map(|v| v.to_string()),
+            pointer_width: defaults.map(|d| d.pointer_width).or_else(|| {
+                cargo_env_var("CARGO_CFG_TARGET_POINTER_WIDTH")
+                    .ok()
+                    .and_then(|width| width.parse().ok())
+            }),
+            max_atomic_width: defaults.and_then(|d| d.max_atomic_width),
+            is_cross_compile: self.get_is_cross_compile()?,
+        })
+    }
+
     fn get_base_ranlib(&self) -> Result<Command, Error> {
         if let Some(ref r) = self.ranlib {
             return Ok(self.cmd(&**r));
@@ -3417,7 +4586,13 @@ impl Build {
                 cmd
             })
             .or_else(|| {
-                if target.os == "emscripten" {
+                if let Some(zig) = self.zig_path() {
+                    // E.g. `zig ar`.
+                    let mut cmd = self.cmd(&zig);
+                    cmd.arg(tool);
+                    name = zig;
+                    Some(cmd)
+                } else if target.os == "emscripten" {
                     // Windows use bat files so we have to be a bit more specific
                     if cfg!(windows) {
                         let mut cmd = self.cmd("cmd");
@@ -3428,6 +4603,9 @@ impl Build {
                         name = format!("em{tool}").into();
                         Some(self.cmd(&name))
                     }
+                } else if let Some(path) = self.wasi_sdk_tool(&target, &format!("llvm-{tool}")) {
+                    name = path;
+                    Some(self.cmd(&name))
                 } else if target.arch == "wasm32" || target.arch == "wasm64" {
                     // Formally speaking one should be able to use this approach,
                     // parsing -print-search-dirs output, to cover all clang targets,
@@ -3452,12 +4630,17 @@ impl Build {
             None => {
                 if target.os == "android" {
                     name = format!("llvm-{tool}").into();
-                    match Command::new(&name).arg("--version").status() {
-                        Ok(status) if status.success() => (),
-                        _ => {
-                            // FIXME: Use parsed target.
-                            let raw_target = self.get_raw_target()?;
-                            name = format!("{}-{}", raw_target.replace("armv7", "arm"), tool).into()
+                    if let Some(path) = self.android_ndk_tool(&format!("llvm-{tool}")) {
+                        name = path;
+                    } else {
+                        match Command::new(&name).arg("--version").status() {
+                            Ok(status) if status.success() => (),
+                            _ => {
+                                // FIXME: Use parsed target.
+                                let raw_target = self.get_raw_target()?;
+                                name = format!("{}-{}", raw_target.replace("armv7", "arm"), tool)
+                                    .into()
+                            }
                         }
                     }
                     self.cmd(&name)
@@ -3551,7 +4734,15 @@ impl Build {
                                     status.success().then_some(target_p)
                                 })
                                 .next()
-                                .unwrap_or_else(|| tool.to_string());
+                                .unwrap_or_else(|| {
+                                    // The host's binutils don't know Elbrus objects, so
+                                    // point at the missing cross tool instead.
+                                    if target.arch == "e2k" {
+                                        format!("{prefix}-{tool}")
+                                    } else {
+                                        tool.to_string()
+                                    }
+                                });
                             name = chosen.into();
                             self.cmd(&name)
                         }
@@ -3615,6 +4806,21 @@ impl Build {
                     "thumbv7neon-unknown-linux-gnueabihf" => Some("arm-linux-gnueabihf"),
                     "thumbv7neon-unknown-linux-musleabihf" => Some("arm-linux-musleabihf"),
                     "armv7-unknown-netbsd-eabihf" => Some("armv7--netbsdelf-eabihf"),
+                    "e2k-unknown-linux-gnu"
+                    | "e2k12c-unknown-linux-gnu"
+                    | "e2k16c-unknown-linux-gnu"
+                    | "e2k1cplus-unknown-linux-gnu"
+                    | "e2k2c3-unknown-linux-gnu"
+                    | "e2k48c-unknown-linux-gnu"
+                    | "e2k4c-unknown-linux-gnu"
+                    | "e2k8c-unknown-linux-gnu"
+                    | "e2k8c2-unknown-linux-gnu"
+                    | "e2k8v7-unknown-linux-gnu"
+                    | "e2kv3-unknown-linux-gnu"
+                    | "e2kv4-unknown-linux-gnu"
+                    | "e2kv5-unknown-linux-gnu"
+                    | "e2kv6-unknown-linux-gnu"
+                    | "e2kv7-unknown-linux-gnu" => self.find_working_gnu_prefix(E2K_PREFIXES),
                     "hexagon-unknown-linux-musl" => Some("hexagon-linux-musl"),
                     "i586-unknown-linux-musl" => Some("musl"),
                     "i686-pc-windows-gnu" => Some("i686-w64-mingw32"),
@@ -3768,7 +4974,74 @@ impl Build {
             .or_else(|| prefixes.first().copied())
     }
 
+    /// The sysroot of an MCST cross-compiler for Elbrus, such as
+    /// `e2k-linux-gcc`, which has to be passed explicitly.
+    ///
+    /// It is looked for in the installation root of the cross-compiler, i.e.
+    /// the parent of the `bin` directory it is in, at `<root>/<prefix>/sysroot`
+    /// or `<root>/sysroot`.
+    fn e2k_cross_sysroot(&self, compiler: &Path) -> Option<PathBuf> {
+        let file_name = compiler.file_name()?.to_str()?;
+        let prefix = E2K_PREFIXES
+            .iter()
+            .find(|prefix| file_name.starts_with(&format!("{prefix}-")))?;
+        let compiler = self.which(compiler, None)?;
+        // The compiler is often symlinked into a directory on PATH.
+        let compiler = fs::canonicalize(&compiler).unwrap_or(compiler);
+        let root = compiler.parent()?.parent()?;
+        [root.join(prefix).join("sysroot"), root.join("sysroot")]
+            .iter()
+            .find(|sysroot| sysroot.is_dir())
+            .cloned()
+    }
+
+    /// The sysroot and GCC installation of the GNU cross toolchain for
+    /// `prefix`, e.g. `aarch64-linux-gnu`, see `Build::detect_cross_sysroot`.
+    ///
+    /// The GCC installation is returned as the root that `--gcc-toolchain`
+    /// expects, i.e. the one with `lib/gcc-cross/<prefix>` or
+    /// `lib/gcc/<prefix>` in it.
+    fn gnu_cross_toolchain(&self, prefix: &str) -> (Option<PathBuf>, Option<PathBuf>) {
+        let mut roots = Vec::new();
+        if let Some(gcc) = self.which(Path::new(&format!("{prefix}-gcc")), None) {
+            // The compiler is often symlinked into a directory on PATH.
+            let gcc = fs::canonicalize(&gcc).unwrap_or(gcc);
+            if let Some(root) = gcc.parent().and_then(Path::parent) {
+                roots.push(root.to_path_buf());
+            }
+        }
+        if cfg!(unix) {
+            roots.push(PathBuf::from("/usr"));
+        }
+
+        for root in roots {
+            // Debian puts the sysroot directly in `/usr/<prefix>`, and
+            // crosstool-NG and others in a subdirectory of it.
+            let sysroot = [
+                root.join(prefix).join("sysroot"),
+                root.join(prefix).join("libc"),
+                root.join(prefix),
+            ]
+            .iter()
+            .find(|sysroot| {
+                sysroot.join("include").is_dir() || sysroot.join("usr/include").is_dir()
+            })
+            .cloned();
+            let gcc_toolchain = ["gcc-cross", "gcc"]
+                .iter()
+                .any(|dir| root.join("lib").join(dir).join(prefix).is_dir())
+                .then(|| root.clone());
+            if sysroot.is_some() || gcc_toolchain.is_some() {
+                return (sysroot, gcc_toolchain);
+            }
+        }
+        (None, None)
+    }
+
     fn get_target(&self) -> Result<TargetInfo<'_>, Error> {
+        if let Some(spec) = self.get_target_spec()? {
+            return Ok(spec.target_info());
+        }
         match &self.target {
             Some(t) if Some(OsStr::new(&**t)) != cargo_env_var_os("TARGET").as_deref() => {
                 TargetInfo::from_rustc_target(t)
@@ -3783,6 +5056,50 @@ impl Build {
         }
     }
 
+    /// The spec of a custom target, either registered with
+    /// [`Build::register_target`] or read from a target JSON file.
+    fn get_target_spec(&self) -> Result<Option<&TargetSpec>, Error> {
+        let spec = self.target_spec.get_or_init(|| {
+            // Errors about a missing target are left to `get_target`.
+            let name = match self.get_raw_target() {
+                Ok(name) => name,
+                Err(_) => return Ok(None),
+            };
+            if let Some((_, spec)) = self.target_specs.iter().rev().find(|(n, _)| **n == *name) {
+                return Ok(Some(TargetSpec::clone(spec).with_name(&name)));
+            }
+            if name.ends_with(".json") {
+                return TargetSpec::from_file(Path::new(&*name))
+                    .map(|spec| Some(spec.with_name(&name)));
+            }
+            if is_builtin(&name) {
+                return Ok(None);
+            }
+            // Same as how `rustc` looks up targets it doesn't know.
+            let target_path = self.get_env("RUST_TARGET_PATH").unwrap_or_default();
+            for dir in env::split_paths(&target_path) {
+                let path = dir.join(format!("{name}.json"));
+                if path.is_file() {
+                    return TargetSpec::from_file(&path).map(|spec| Some(spec.with_name(&name)));
+                }
+            }
+            Ok(None)
+        });
+        spec.as_ref().map(Option::as_ref).map_err(Clone::clone)
+    }
+
+    /// The LLVM target triple, as passed to Clang with `--target=`.
+    fn get_llvm_target(
+        &self,
+        target: &TargetInfo<'_>,
+        version: Option<&str>,
+    ) -> Result<Cow<'_, str>, Error> {
+        if let Some(spec) = self.get_target_spec()? {
+            return Ok(Cow::Borrowed(&spec.llvm_target));
+        }
+        Ok(target.llvm_target(&self.get_raw_target()?, version))
+    }
+
     fn get_raw_target(&self) -> Result<Cow<'_, str>, Error> {
         match &self.target {
             Some(t) => Ok(Cow::Borrowed(t)),
@@ -3827,12 +5144,32 @@ impl Build {
         }
     }
 
+    /// The path of Zig, if it is the compiler, see `Build::use_zig`.
+    fn zig_path(&self) -> Option<PathBuf> {
+        if let Some(compiler) = &self.compiler {
+            return zig::is_zig(compiler).then(|| compiler.to_path_buf());
+        }
+        let env = if self.cpp { "CXX" } else { "CC" };
+        if let Some((tool, _, _)) = self.env_tool(env) {
+            return zig::is_zig(&tool).then_some(tool);
+        }
+        self.use_zig.then(|| PathBuf::from("zig"))
+    }
+
+    fn get_detect_cross_sysroot(&self) -> bool {
+        self.detect_cross_sysroot
+            .unwrap_or_else(|| self.get_env_boolean("CC_DETECT_CROSS_SYSROOT"))
+    }
+
     fn get_shell_escaped_flags(&self) -> bool {
         self.shell_escaped_flags
             .unwrap_or_else(|| self.get_env_boolean("CC_SHELL_ESCAPED_FLAGS"))
     }
 
     fn get_dwarf_version(&self, family: ToolFamily) -> Option<u32> {
+        if family == ToolFamily::Lcc {
+            return Some(lcc::DWARF_VERSION);
+        }
         // Tentatively matches the DWARF version defaults as of rustc 1.62.
         let target = self.get_target().ok()?;
         if matches!(
@@ -3995,7 +5332,8 @@ impl Build {
         Ok(())
     }
 
-    fn apple_sdk_root_inner(&self, sdk: &str) -> Result<Cow<'_, OsStr>, Error> {
+    fn apple_sdk_root_inner(&self, target: &TargetInfo<'_>) -> Result<Cow<'_, OsStr>, Error> {
+        let sdk = target.apple_sdk_name();
         // Code copied from rustc's compiler/rustc_codegen_ssa/src/back/link.rs.
         if let Some(sdkroot) = self.get_env_overridable("SDKROOT") {
             let p = Path::new(&sdkroot);
@@ -4028,12 +5366,17 @@ impl Build {
             }
         }
 
+        if let Some(sdk) = self.find_apple_sdk(target) {
+            return Ok(Cow::Owned(sdk.path.into()));
+        }
+
         let sdk_path = run_output(
             self.cmd("xcrun")
                 .arg("--show-sdk-path")
                 .arg("--sdk")
                 .arg(sdk),
             &self.cargo_output,
+            CommandKind::Probe,
         )?;
 
         let sdk_path = match String::from_utf8(sdk_path) {
@@ -4049,6 +5392,9 @@ impl Build {
     }
 
     fn apple_sdk_root(&self, target: &TargetInfo<'_>) -> Result<Arc<OsStr>, Error> {
+        if let Some(sysroot) = &self.sysroot {
+            return Ok(sysroot.as_os_str().into());
+        }
         let sdk = target.apple_sdk_name();
 
         if let Some(ret) = self
@@ -4061,7 +5407,7 @@ impl Build {
         {
             return Ok(ret);
         }
-        let sdk_path: Arc<OsStr> = self.apple_sdk_root_inner(sdk)?.into();
+        let sdk_path: Arc<OsStr> = self.apple_sdk_root_inner(target)?.into();
         self.build_cache
             .apple_sdk_root_cache
             .write()
@@ -4070,6 +5416,73 @@ impl Build {
         Ok(sdk_path)
     }
 
+    /// Find the SDK for the target in `SDKROOT` or in the directories in
+    /// `APPLE_SDKS_DIR` from its `SDKSettings.json`, without `xcrun`.
+    fn find_apple_sdk(&self, target: &TargetInfo<'_>) -> Option<AppleSdk> {
+        let sdk = target.apple_sdk_name();
+        // Mac Catalyst uses the macOS SDK, but with iOS versions.
+        let supported_target = if target.env == "macabi" {
+            "iosmac"
+        } else {
+            sdk
+        };
+        if let Some(sdkroot) = self.get_env_overridable("SDKROOT") {
+            if let Some(sdk) = AppleSdk::read(Path::new(&sdkroot), sdk, supported_target) {
+                return Some(sdk);
+            }
+        }
+        let roots = self.get_env("APPLE_SDKS_DIR")?;
+        AppleSdk::find(env::split_paths(&roots), sdk, supported_target)
+    }
+
+    /// Find the NDK in `ANDROID_NDK_HOME` or `ANDROID_NDK_ROOT`, or the
+    /// newest one in `ANDROID_HOME/ndk`.
+    fn find_android_ndk(&self) -> Option<AndroidNdk> {
+        for var in ["ANDROID_NDK_HOME", "ANDROID_NDK_ROOT"] {
+            if let Some(path) = self.get_env_overridable(var) {
+                if let Some(ndk) = AndroidNdk::read(Path::new(&path)) {
+                    return Some(ndk);
+                }
+            }
+        }
+        let home = self.get_env_overridable("ANDROID_HOME")?;
+        AndroidNdk::find_newest(&Path::new(&home).join("ndk"))
+    }
+
+    /// The path of `tool`, e.g. `clang` or `llvm-ar`, in the NDK's prebuilt
+    /// toolchain for the host.
+    fn android_ndk_tool(&self, tool: &str) -> Option<PathBuf> {
+        let host = match &self.host {
+            Some(h) => Cow::Borrowed(&**h),
+            None => Cow::Owned(cargo_env_var("HOST").ok()?),
+        };
+        let bin = self.find_android_ndk()?.bin_dir(&host)?;
+        self.which(&bin.join(tool), None)
+    }
+
+    /// The API level to build for on Android, from
+    /// [`android_api_level`](Build::android_api_level), `ANDROID_PLATFORM`
+    /// or else the lowest one supported by the NDK, if one is found.
+    fn get_android_api_level(&self, target: &TargetInfo<'_>) -> Option<u32> {
+        if target.os != "android" {
+            return None;
+        }
+        if let Some(api_level) = self.android_api_level {
+            return Some(api_level);
+        }
+        if let Some(platform) = self.get_env_overridable("ANDROID_PLATFORM") {
+            match platform.to_str().and_then(android_ndk::parse_platform) {
+                Some(api_level) => return Some(api_level),
+                None => self.cargo_output.print_warning(&format_args!(
+                    "unknown ANDROID_PLATFORM {}, expected e.g. android-21",
+                    platform.to_string_lossy()
+                )),
+            }
+        }
+        let min_api_level = self.find_android_ndk()?.min_api_level;
+        Some(min_api_level.max(android_ndk::arch_min_api_level(target.full_arch)))
+    }
+
     fn apple_deployment_target(&self, target: &TargetInfo<'_>) -> Arc<str> {
         let sdk = target.apple_sdk_name();
         if let Some(ret) = self
@@ -4084,12 +5497,24 @@ impl Build {
         }
 
         let default_deployment_from_sdk = || -> Option<Arc<str>> {
+            if let Some(apple_sdk) = self.find_apple_sdk(target) {
+                let default = apple_sdk.default_deployment_target?;
+                // The SDK can't build for older versions than it supports.
+                return Some(match apple_sdk.minimum_deployment_target {
+                    Some(minimum) if apple_sdk::compare_versions(&default, &minimum).is_lt() => {
+                        minimum.into()
+                    }
+                    _ => default.into(),
+                });
+            }
+
             let version = run_output(
                 self.cmd("xcrun")
                     .arg("--show-sdk-version")
                     .arg("--sdk")
                     .arg(sdk),
                 &self.cargo_output,
+                CommandKind::Probe,
             )
             .ok()?;
 
@@ -4199,7 +5624,9 @@ impl Build {
     }
 
     fn wasm_musl_sysroot(&self) -> Result<OsString, Error> {
-        if let Some(musl_sysroot_path) = self.get_env("WASM_MUSL_SYSROOT") {
+        if let Some(sysroot) = &self.sysroot {
+            Ok(sysroot.as_os_str().to_owned())
+        } else if let Some(musl_sysroot_path) = self.get_env("WASM_MUSL_SYSROOT") {
             Ok(musl_sysroot_path)
         } else {
             Err(Error::new(
@@ -4209,15 +5636,60 @@ impl Build {
         }
     }
 
-    fn wasi_sysroot(&self) -> Result<OsString, Error> {
+    /// The WASI sysroot from `WASI_SYSROOT`, wasi-sdk, or where WASI
+    /// sysroots are usually installed on their own.
+    fn wasi_sysroot(&self) -> Result<PathBuf, Error> {
+        if let Some(sysroot) = &self.sysroot {
+            return Ok(sysroot.to_path_buf());
+        }
         if let Some(wasi_sysroot_path) = self.get_env("WASI_SYSROOT") {
-            Ok(wasi_sysroot_path)
-        } else {
-            Err(Error::new(
-                ErrorKind::EnvVarNotFound,
-                "Environment variable WASI_SYSROOT not defined. Download sysroot from GitHub & setup environment variable WASI_SYSROOT targeting the folder.",
-            ))
+            return Ok(wasi_sysroot_path.into());
+        }
+        let sdk_locations = self.wasi_sdk_locations();
+        if let Some(sdk) = WasiSdk::find(sdk_locations.iter().cloned()) {
+            return Ok(sdk.sysroot());
+        }
+        let sysroot_locations = wasi_sdk::sysroot_locations();
+        if let Some(sysroot) = sysroot_locations.iter().find(|path| path.is_dir()) {
+            return Ok(sysroot.clone());
+        }
+
+        let mut message = String::from(
+            "Could not find a WASI sysroot. Install wasi-sdk from \
+             https://github.com/WebAssembly/wasi-sdk and set WASI_SDK_PATH to it, \
+             or set WASI_SYSROOT. Tried:\n  - WASI_SYSROOT (not set)",
+        );
+        if self.get_env_overridable("WASI_SDK_PATH").is_none() {
+            message.push_str("\n  - WASI_SDK_PATH (not set)");
+        }
+        for path in sdk_locations
+            .iter()
+            .map(|sdk| WasiSdk { path: sdk.clone() }.sysroot())
+            .chain(sysroot_locations)
+        {
+            message.push_str(&format!("\n  - {}", path.display()));
+        }
+        Err(Error::new(ErrorKind::ToolNotFound, message))
+    }
+
+    /// `WASI_SDK_PATH`, then the usual install locations of wasi-sdk.
+    fn wasi_sdk_locations(&self) -> Vec<PathBuf> {
+        let mut locations = Vec::new();
+        if let Some(path) = self.get_env_overridable("WASI_SDK_PATH") {
+            locations.push(PathBuf::from(&*path));
         }
+        locations.extend(wasi_sdk::sdk_locations());
+        locations
+    }
+
+    /// The path of `tool`, e.g. `clang` or `llvm-ar`, in wasi-sdk, if the
+    /// target is a WASI one and wasi-sdk is found.
+    fn wasi_sdk_tool(&self, target: &TargetInfo<'_>, tool: &str) -> Option<PathBuf> {
+        if target.os != "wasi" {
+            return None;
+        }
+        let sdk = WasiSdk::find(self.wasi_sdk_locations())?;
+        self.which(&sdk.bin_dir().join(tool), None)
     }
 
     fn cuda_file_count(&self) -> usize {
@@ -4254,6 +5726,7 @@ impl Build {
             self.cmd(cc).arg("--print-search-dirs"),
             // this doesn't concern the compilation so we always want to show warnings.
             cargo_output,
+            CommandKind::Probe,
         )
         .ok()?;
         // clang driver appears to be forcing UTF-8 output even on Windows,
@@ -4293,24 +5766,23 @@ impl Build {
 
     /// Compiling for WASI targets typically uses the [wasi-sdk] project and
     /// installations of wasi-sdk are typically indicated with the
-    /// `WASI_SDK_PATH` environment variable. Check to see if that environment
-    /// variable exists, and check to see if an appropriate compiler is located
-    /// there. If that all passes then use that compiler by default, but
-    /// otherwise fall back to whatever the clang default is since gcc doesn't
-    /// have support for compiling to wasm.
+    /// `WASI_SDK_PATH` environment variable, or are in a common location
+    /// such as `/opt/wasi-sdk`. Check to see if an appropriate compiler is
+    /// located there, preferring the one named after the target. If that all
+    /// passes then use that compiler by default, but otherwise fall back to
+    /// whatever the clang default is since gcc doesn't have support for
+    /// compiling to wasm.
     ///
     /// [wasi-sdk]: https://github.com/WebAssembly/wasi-sdk
-    fn autodetect_wasi_compiler(&self, raw_target: &str, clang: &str) -> PathBuf {
-        if let Some(path) = self.get_env_overridable("WASI_SDK_PATH") {
-            let target_clang = Path::new(&path)
-                .join("bin")
-                .join(format!("{raw_target}-clang"));
-            if let Some(path) = self.which(&target_clang, None) {
-                return path;
-            }
-        }
-
-        clang.into()
+    fn autodetect_wasi_compiler(
+        &self,
+        target: &TargetInfo<'_>,
+        raw_target: &str,
+        clang: &str,
+    ) -> PathBuf {
+        self.wasi_sdk_tool(target, &format!("{raw_target}-{clang}"))
+            .or_else(|| self.wasi_sdk_tool(target, clang))
+            .unwrap_or_else(|| clang.into())
     }
 }
 
@@ -4325,6 +5797,44 @@ fn fail(s: &str) -> ! {
     std::process::exit(1);
 }
 
+/// Turn the failures collected while compiling `objs` with
+/// [`Build::keep_going`] into a single error listing every failing source.
+fn keep_going_result(objs: &[Object], mut failures: Vec<(usize, Error)>) -> Result<(), Error> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+    failures.sort_by_key(|(i, _)| *i);
+    let mut message = format!(
+        "{} of {} source files failed to compile:",
+        failures.len(),
+        objs.len()
+    );
+    for (i, err) in &failures {
+        message.push_str(&format!("\n\n{}: {}", objs[*i].src.display(), err.message));
+    }
+    Err(Error::new(ErrorKind::ToolExecError, message))
+}
+
+/// Make the library `dst` also available as `{lib_name}.lib`, which is what
+/// the MSVC linker is passed.
+fn link_msvc_lib(lib_name: &str, dst: &Path) -> Result<(), Error> {
+    let lib_dst = dst.with_file_name(format!("{lib_name}.lib"));
+    let _ = fs::remove_file(&lib_dst);
+    match fs::hard_link(dst, &lib_dst).or_else(|_| {
+        // if hard-link fails, just copy (ignoring the number of bytes written)
+        fs::copy(dst, &lib_dst).map(|_| ())
+    }) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::new(
+            ErrorKind::IOError,
+            "Could not copy or create a hard-link to the generated lib file.",
+        )),
+    }
+}
+
+/// The prefixes of the MCST cross-compilers for Elbrus, most likely first.
+const E2K_PREFIXES: &[&str] = &["e2k-linux", "e2k-mcst-linux-gnu"];
+
 // Use by default minimum available API level
 // See note about naming here
 // https://android.googlesource.com/platform/ndk/+/refs/heads/ndk-release-r21/docs/BuildSystemMaintainers.md#Clang
@@ -0,0 +1,285 @@
+//! Generation of Ninja build files, see
+//! [`Build::write_ninja`](crate::Build::write_ninja).
+
+use std::ffi::OsStr;
+use std::iter;
+use std::path::Path;
+use std::process::Command;
+
+use crate::{BuildPlan, Object};
+
+/// How the compiler reports the headers that an object file depends on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Deps {
+    /// A Makefile-style depfile next to the object file, with `-MD -MF`.
+    Gcc,
+    /// `/showIncludes` notes, which Ninja filters out of the output.
+    Msvc,
+    /// Not at all, e.g. the MSVC assemblers.
+    None,
+}
+
+impl Deps {
+    fn rule(self) -> &'static str {
+        match self {
+            Deps::Gcc => "cc",
+            Deps::Msvc => "cc_msvc",
+            Deps::None => "cc_nodeps",
+        }
+    }
+}
+
+const RULES: &str = "\
+ninja_required_version = 1.3
+
+rule cc
+  command = $cmd
+  description = CC $out
+  depfile = $out.d
+  deps = gcc
+
+rule cc_msvc
+  command = $cmd
+  description = CC $out
+  deps = msvc
+
+rule cc_nodeps
+  command = $cmd
+  description = CC $out
+
+rule ar
+  command = $cmd
+  description = AR $out
+";
+
+/// Render `plan` as a Ninja build file, with `extra_objects` added to the
+/// library as they are.
+pub(crate) fn build_file(
+    plan: &BuildPlan,
+    extra_objects: &[&Path],
+    deps: &dyn Fn(&Object) -> Deps,
+) -> String {
+    let mut file = String::from("# Generated by the `cc` crate, do not edit.\n\n");
+    file.push_str(RULES);
+
+    for (obj, cmd) in plan.objects.iter().zip(&plan.compile_commands) {
+        let deps = deps(obj);
+        let mut depfile = obj.dst.clone().into_os_string();
+        depfile.push(".d");
+        let flags: Vec<&OsStr> = match deps {
+            Deps::Gcc => vec!["-MD".as_ref(), "-MF".as_ref(), &depfile],
+            Deps::Msvc => vec!["/showIncludes".as_ref()],
+            Deps::None => vec![],
+        };
+        file.push_str(&format!(
+            "\nbuild {}: {} {}\n  cmd = {}\n",
+            escape_path(&obj.dst),
+            deps.rule(),
+            escape_path(&obj.src),
+            escape_value(&shell::command(&[command_line(cmd, &flags)])),
+        ));
+    }
+
+    if !plan.archive_commands.is_empty() {
+        let inputs = plan
+            .objects
+            .iter()
+            .map(|obj| obj.dst.as_path())
+            .chain(extra_objects.iter().copied())
+            .map(escape_path)
+            .collect::<Vec<_>>()
+            .join(" ");
+        // `ar cq` appends, so the library is removed first. The symbol index
+        // is added by the same edge, as Ninja requires one edge per output.
+        let cmds: Vec<_> = iter::once(shell::remove(&plan.library))
+            .chain(
+                plan.archive_commands
+                    .iter()
+                    .map(|cmd| command_line(cmd, &[])),
+            )
+            .collect();
+        file.push_str(&format!(
+            "\nbuild {}: ar {}\n  cmd = {}\n",
+            escape_path(&plan.library),
+            inputs,
+            escape_value(&shell::command(&cmds)),
+        ));
+        file.push_str(&format!("\ndefault {}\n", escape_path(&plan.library)));
+    }
+
+    file
+}
+
+/// Render `cmd` for the shell, with `flags` inserted before a `--` that
+/// separates the input files, or at the end.
+fn command_line(cmd: &Command, flags: &[&OsStr]) -> String {
+    let args: Vec<&OsStr> = cmd.get_args().collect();
+    let split = args
+        .iter()
+        .position(|arg| *arg == "--")
+        .unwrap_or(args.len());
+    let args = args[..split]
+        .iter()
+        .chain(flags)
+        .chain(&args[split..])
+        .map(|arg| shell::quote(arg));
+
+    let envs: Vec<_> = cmd.get_envs().collect();
+    shell::env_prefix(&envs)
+        .into_iter()
+        .chain(iter::once(shell::quote(cmd.get_program())))
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escape a path in a `build` line.
+fn escape_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('$', "$$")
+        .replace(' ', "$ ")
+        .replace(':', "$:")
+}
+
+/// Escape the value of a variable.
+fn escape_value(value: &str) -> String {
+    value.replace('$', "$$")
+}
+
+/// Ninja runs commands with `/bin/sh -c`.
+#[cfg(not(windows))]
+mod shell {
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    pub(super) fn quote(arg: &OsStr) -> String {
+        let arg = arg.to_string_lossy();
+        let is_plain = |b: u8| b.is_ascii_alphanumeric() || b"%+,-./:=@_".contains(&b);
+        if !arg.is_empty() && arg.bytes().all(is_plain) {
+            return arg.into_owned();
+        }
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+
+    /// A prefix of a command line which sets the environment variables in
+    /// `envs`, or removes them if the value is `None`.
+    pub(super) fn env_prefix(envs: &[(&OsStr, Option<&OsStr>)]) -> Option<String> {
+        if envs.is_empty() {
+            return None;
+        }
+        let mut prefix = String::from("env");
+        for (key, _) in envs.iter().filter(|(_, value)| value.is_none()) {
+            prefix.push_str(" -u ");
+            prefix.push_str(&quote(key));
+        }
+        for (key, value) in envs {
+            if let Some(value) = value {
+                let mut assignment = key.to_os_string();
+                assignment.push("=");
+                assignment.push(value);
+                prefix.push(' ');
+                prefix.push_str(&quote(&assignment));
+            }
+        }
+        Some(prefix)
+    }
+
+    pub(super) fn remove(path: &Path) -> String {
+        format!("rm -f {}", quote(path.as_os_str()))
+    }
+
+    pub(super) fn command(cmds: &[String]) -> String {
+        cmds.join(" && ")
+    }
+}
+
+/// Ninja runs commands directly with `CreateProcess`, so anything more than a
+/// plain command goes through `cmd /c`.
+#[cfg(windows)]
+mod shell {
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    pub(super) fn quote(arg: &OsStr) -> String {
+        let arg = arg.to_string_lossy();
+        if !arg.is_empty() && !arg.contains(|c| " \t\"&|<>^()".contains(c)) {
+            return arg.into_owned();
+        }
+        // See `CommandLineToArgvW` for how backslashes are interpreted.
+        let mut quoted = String::from('"');
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            if c == '\\' {
+                backslashes += 1;
+                continue;
+            }
+            let escaped = if c == '"' {
+                2 * backslashes + 1
+            } else {
+                backslashes
+            };
+            quoted.extend(std::iter::repeat('\\').take(escaped));
+            quoted.push(c);
+            backslashes = 0;
+        }
+        quoted.extend(std::iter::repeat('\\').take(2 * backslashes));
+        quoted.push('"');
+        quoted
+    }
+
+    /// A prefix of a command line which sets the environment variables in
+    /// `envs`, or removes them if the value is `None`.
+    pub(super) fn env_prefix(envs: &[(&OsStr, Option<&OsStr>)]) -> Option<String> {
+        if envs.is_empty() {
+            return None;
+        }
+        let sets: Vec<_> = envs
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "set \"{}={}\" &&",
+                    key.to_string_lossy(),
+                    value.unwrap_or_default().to_string_lossy()
+                )
+            })
+            .collect();
+        Some(sets.join(" "))
+    }
+
+    pub(super) fn remove(path: &Path) -> String {
+        format!("(del /f /q {} 2>nul || cd .)", quote(path.as_os_str()))
+    }
+
+    pub(super) fn command(cmds: &[String]) -> String {
+        match cmds {
+            [cmd] if !cmd.starts_with("set ") => cmd.clone(),
+            _ => format!("cmd /c {}", cmds.join(" && ")),
+        }
+    }
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unix_command_line() {
+        let mut cmd = Command::new("cc");
+        cmd.arg("-DX=\"a b\"")
+            .arg("it's")
+            .arg("--")
+            .arg("foo.c")
+            .env("ZERO_AR_DATE", "1")
+            .env_remove("CFLAGS");
+        assert_eq!(
+            command_line(&cmd, &["/showIncludes".as_ref()]),
+            r#"env -u CFLAGS ZERO_AR_DATE=1 cc '-DX="a b"' 'it'\''s' /showIncludes -- foo.c"#
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(escape_path(Path::new("C:/a b/$x.o")), "C$:/a$ b/$$x.o");
+        assert_eq!(escape_value("echo $HOME"), "echo $$HOME");
+    }
+}
@@ -0,0 +1,77 @@
+//! Routing of everything `cc` prints, see [`OutputSink`].
+
+use std::fmt;
+use std::io::{self, Write};
+
+/// Receives the output of a [`Build`](crate::Build).
+///
+/// By default, `cc` prints everything to stdout for Cargo to interpret, as
+/// implemented by [`StdoutSink`]. A custom sink can be set with
+/// [`Build::output_sink`](crate::Build::output_sink), e.g. to use `cc`
+/// outside of a build script or to inspect the output in tests.
+///
+/// Which events are produced is still controlled by
+/// [`Build::cargo_metadata`](crate::Build::cargo_metadata),
+/// [`Build::cargo_warnings`](crate::Build::cargo_warnings),
+/// [`Build::cargo_debug`](crate::Build::cargo_debug) and
+/// [`Build::cargo_output`](crate::Build::cargo_output).
+///
+/// The methods may be called from multiple threads at once when compiling in
+/// parallel.
+pub trait OutputSink: Send + Sync {
+    /// A line of Cargo metadata, e.g. `cargo:rustc-link-lib=static=foo`.
+    fn metadata(&self, line: &str);
+
+    /// A warning from `cc` itself, without the `cargo:warning=` prefix.
+    fn warning(&self, message: &str);
+
+    /// Debug output, such as the commands being run.
+    fn debug(&self, message: &str);
+
+    /// Output that the compiler or archiver printed to stdout.
+    fn compiler_stdout(&self, output: &[u8]);
+
+    /// A line that the compiler or archiver printed to stderr, without the
+    /// trailing newline. These are usually diagnostics.
+    fn compiler_stderr(&self, line: &[u8]);
+}
+
+impl fmt::Debug for dyn OutputSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutputSink").finish_non_exhaustive()
+    }
+}
+
+/// The default [`OutputSink`], which prints everything to stdout in the
+/// format expected from build scripts.
+///
+/// Compiler diagnostics are turned into `cargo:warning=` lines, so that Cargo
+/// shows them.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink {
+    fn metadata(&self, line: &str) {
+        println!("{line}");
+    }
+
+    fn warning(&self, message: &str) {
+        println!("cargo:warning={message}");
+    }
+
+    fn debug(&self, message: &str) {
+        println!("{message}");
+    }
+
+    fn compiler_stdout(&self, output: &[u8]) {
+        let _ = io::stdout().lock().write_all(output);
+    }
+
+    fn compiler_stderr(&self, line: &[u8]) {
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        stdout.write_all(b"cargo:warning=").unwrap();
+        stdout.write_all(line).unwrap();
+        stdout.write_all(b"\n").unwrap();
+    }
+}
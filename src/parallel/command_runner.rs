@@ -1,7 +1,7 @@
 use std::{
     cell::Cell,
-    io::{self, Write as _},
-    process::{Child, Command},
+    process::{Child, Command, Output},
+    thread,
 };
 
 use crate::{
@@ -9,10 +9,11 @@ use crate::{
         async_executor::{block_on, YieldOnce},
         job_token,
     },
-    spawn, CargoOutput, Error, ErrorKind, StderrForwarder,
+    spawn, with_diagnostics, CargoOutput, CommandExecutor, CommandKind, Error, ErrorKind,
+    StderrForwarder, StdoutForwarder,
 };
 
-struct KillOnDrop(Child, StderrForwarder);
+struct KillOnDrop(Child, StderrForwarder, Option<StdoutForwarder>);
 
 impl Drop for KillOnDrop {
     fn drop(&mut self) {
@@ -34,17 +35,20 @@ where
 
 fn try_wait_on_child(
     cmd: &Command,
-    child: &mut Child,
-    mut stdout: impl io::Write,
-    stderr_forwarder: &mut StderrForwarder,
+    child: &mut KillOnDrop,
+    cargo_output: &CargoOutput,
 ) -> Result<Option<()>, Error> {
+    let KillOnDrop(child, stderr_forwarder, stdout_forwarder) = child;
     stderr_forwarder.forward_available();
 
     match child.try_wait() {
         Ok(Some(status)) => {
             stderr_forwarder.forward_all();
+            if let Some(stdout_forwarder) = stdout_forwarder.take() {
+                stdout_forwarder.forward(cargo_output);
+            }
 
-            let _ = writeln!(stdout, "{}", status);
+            cargo_output.print_compiler_stdout(format!("{status}\n").as_bytes());
 
             if status.success() {
                 Ok(Some(()))
@@ -66,10 +70,16 @@ fn try_wait_on_child(
     }
 }
 
+/// Run `cmds` in parallel, returning the last error.
+///
+/// With `keep_going`, the commands keep running even if some can't be
+/// created or started, and the errors of all failed commands are returned
+/// together with their index, including their diagnostics.
 pub(crate) fn run_commands_in_parallel(
     cargo_output: &CargoOutput,
     cmds: &mut dyn Iterator<Item = Result<Command, Error>>,
-) -> Result<(), Error> {
+    keep_going: bool,
+) -> Result<Vec<(usize, Error)>, Error> {
     // Limit our parallelism globally with a jobserver.
     let mut tokens = job_token::ActiveJobTokenServer::new();
 
@@ -92,14 +102,13 @@ pub(crate) fn run_commands_in_parallel(
     // acquire the appropriate tokens, Once all objects have been compiled
     // we wait on all the processes and propagate the results of compilation.
 
-    let pendings = Cell::new(Vec::<(Command, KillOnDrop, job_token::JobToken)>::new());
+    let pendings = Cell::new(Vec::<(usize, Command, KillOnDrop, job_token::JobToken)>::new());
+    let failures = Cell::new(Vec::new());
     let is_disconnected = Cell::new(false);
     let has_made_progress = Cell::new(false);
 
     let wait_future = async {
         let mut error = None;
-        // Buffer the stdout
-        let mut stdout = io::BufWriter::with_capacity(128, io::stdout());
 
         loop {
             // If the other end of the pipe is already disconnected, then we're not gonna get any new jobs,
@@ -113,8 +122,8 @@ pub(crate) fn run_commands_in_parallel(
 
             cell_update(&pendings, |mut pendings| {
                 // Try waiting on them.
-                pendings.retain_mut(|(cmd, child, _token)| {
-                    match try_wait_on_child(cmd, &mut child.0, &mut stdout, &mut child.1) {
+                pendings.retain_mut(|(index, cmd, child, _token)| {
+                    match try_wait_on_child(cmd, child, cargo_output) {
                         Ok(Some(())) => {
                             // Task done, remove the entry
                             has_made_progress.set(true);
@@ -127,10 +136,16 @@ pub(crate) fn run_commands_in_parallel(
                             // sure users always see all the compilation failures.
                             has_made_progress.set(true);
 
-                            if cargo_output.warnings {
-                                let _ = writeln!(stdout, "cargo:warning={}", err);
+                            cargo_output.print_warning(&err);
+                            if keep_going {
+                                let err = with_diagnostics(err, &child.1.take_captured());
+                                cell_update(&failures, |mut failures| {
+                                    failures.push((*index, err));
+                                    failures
+                                });
+                            } else {
+                                error = Some(err);
                             }
-                            error = Some(err);
 
                             false
                         }
@@ -152,15 +167,40 @@ pub(crate) fn run_commands_in_parallel(
         }
     };
     let spawn_future = async {
-        for res in cmds {
-            let mut cmd = res?;
-            let token = tokens.acquire().await?;
-            let mut child = spawn(&mut cmd, cargo_output)?;
-            let mut stderr_forwarder = StderrForwarder::new(&mut child);
+        for (index, res) in cmds.enumerate() {
+            let spawned = match res {
+                Ok(mut cmd) => {
+                    let token = tokens.acquire().await?;
+                    spawn(&mut cmd, cargo_output, CommandKind::Compile)
+                        .map(|child| (cmd, child, token))
+                }
+                Err(err) => Err(err),
+            };
+            let (cmd, mut child, token) = match spawned {
+                Ok(spawned) => spawned,
+                Err(err) if keep_going => {
+                    cell_update(&failures, |mut failures| {
+                        failures.push((index, err));
+                        failures
+                    });
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let stdout_forwarder = StdoutForwarder::new(&mut child, cargo_output);
+            let mut stderr_forwarder = StderrForwarder::new(&mut child, cargo_output);
             stderr_forwarder.set_non_blocking()?;
+            if keep_going {
+                stderr_forwarder.capture();
+            }
 
             cell_update(&pendings, |mut pendings| {
-                pendings.push((cmd, KillOnDrop(child, stderr_forwarder), token));
+                pendings.push((
+                    index,
+                    cmd,
+                    KillOnDrop(child, stderr_forwarder, Some(stdout_forwarder)),
+                    token,
+                ));
                 pendings
             });
 
@@ -171,5 +211,52 @@ pub(crate) fn run_commands_in_parallel(
         Ok::<_, Error>(())
     };
 
-    block_on(wait_future, spawn_future, &has_made_progress)
+    block_on(wait_future, spawn_future, &has_made_progress)?;
+    Ok(failures.take())
+}
+
+/// Run the probes `cmds` concurrently, as far as the jobserver allows, and
+/// collect their output.
+pub(crate) fn outputs_in_parallel(
+    cmds: &mut [Command],
+    executor: &dyn CommandExecutor,
+) -> Vec<Result<Output, Error>> {
+    let mut tokens = job_token::ActiveJobTokenServer::new();
+
+    thread::scope(|s| {
+        let handles: Vec<_> = cmds
+            .iter_mut()
+            .map(|cmd| {
+                let token = acquire_token(&mut tokens)?;
+                Ok(s.spawn(move || {
+                    let output = executor
+                        .spawn(cmd, CommandKind::Probe)
+                        .and_then(|child| child.wait_with_output());
+                    drop(token);
+                    output
+                }))
+            })
+            .collect::<Vec<Result<_, Error>>>();
+
+        handles
+            .into_iter()
+            .map(|handle| Ok(handle?.join().unwrap()?))
+            .collect()
+    })
+}
+
+fn acquire_token(
+    tokens: &mut job_token::ActiveJobTokenServer,
+) -> Result<job_token::JobToken, Error> {
+    let has_made_progress = Cell::new(false);
+    let mut token = None;
+    block_on(
+        async {
+            token = Some(tokens.acquire().await?);
+            Ok(())
+        },
+        async { Ok(()) },
+        &has_made_progress,
+    )?;
+    Ok(token.unwrap())
 }
@@ -3,4 +3,4 @@ mod command_runner;
 mod job_token;
 pub(crate) mod stderr;
 
-pub(crate) use command_runner::run_commands_in_parallel;
+pub(crate) use command_runner::{outputs_in_parallel, run_commands_in_parallel};
@@ -0,0 +1,87 @@
+//! Dry runs of [`Build::try_compile`](crate::Build::try_compile), see
+//! [`BuildPlan`].
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+use crate::{Object, OutputSink, StdoutSink};
+
+/// The commands that [`Build::compile`](crate::Build::compile) would run,
+/// and the Cargo metadata it would print, as returned by
+/// [`Build::plan`](crate::Build::plan).
+///
+/// The commands are in the order they would be run in, except that compile
+/// commands may run in parallel with the `parallel` feature.
+#[derive(Debug, Default)]
+pub struct BuildPlan {
+    pub(crate) compile_commands: Vec<Command>,
+    pub(crate) archive_commands: Vec<Command>,
+    pub(crate) metadata: Vec<String>,
+    /// The object files in the same order as `compile_commands`.
+    pub(crate) objects: Vec<Object>,
+    /// The static library created by `archive_commands`.
+    pub(crate) library: PathBuf,
+}
+
+impl BuildPlan {
+    /// The commands compiling each source file into an object file.
+    pub fn compile_commands(&self) -> &[Command] {
+        &self.compile_commands
+    }
+
+    /// The commands creating the static library from the object files,
+    /// including the CUDA device link step if any.
+    pub fn archive_commands(&self) -> &[Command] {
+        &self.archive_commands
+    }
+
+    /// The lines of Cargo metadata, such as `cargo:rustc-link-lib=static=foo`.
+    pub fn metadata(&self) -> &[String] {
+        &self.metadata
+    }
+
+    /// Iterate over all commands, in order.
+    pub fn commands(&self) -> impl Iterator<Item = &Command> {
+        self.compile_commands
+            .iter()
+            .chain(self.archive_commands.iter())
+    }
+}
+
+/// Records metadata into a [`BuildPlan`], and passes everything else on.
+pub(crate) struct PlanSink {
+    pub(crate) plan: Arc<Mutex<BuildPlan>>,
+    pub(crate) inner: Option<Arc<dyn OutputSink>>,
+}
+
+impl PlanSink {
+    fn inner(&self) -> &dyn OutputSink {
+        match &self.inner {
+            Some(sink) => &**sink,
+            None => &StdoutSink,
+        }
+    }
+}
+
+impl OutputSink for PlanSink {
+    fn metadata(&self, line: &str) {
+        self.plan.lock().unwrap().metadata.push(line.to_string());
+    }
+
+    fn warning(&self, message: &str) {
+        self.inner().warning(message);
+    }
+
+    fn debug(&self, message: &str) {
+        self.inner().debug(message);
+    }
+
+    fn compiler_stdout(&self, output: &[u8]) {
+        self.inner().compiler_stdout(output);
+    }
+
+    fn compiler_stderr(&self, line: &[u8]) {
+        self.inner().compiler_stderr(line);
+    }
+}
@@ -0,0 +1,211 @@
+//! Persistent on-disk cache for the results of probing the compiler, shared
+//! between build scripts.
+//!
+//! Entries are stored in a single file, which is only modified while holding
+//! a lock file in the same directory. Since the file is replaced atomically,
+//! it can be read without taking the lock.
+
+use std::collections::HashMap;
+use std::env;
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HEADER: &str = "cc-probe-cache v1";
+const CACHE_FILE: &str = "probes.txt";
+const LOCK_FILE: &str = "probes.lock";
+
+/// Locks older than this are assumed to be left over by a crashed process.
+const STALE_LOCK: Duration = Duration::from_secs(30);
+/// Give up on updating the cache after waiting this long for the lock.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub(crate) struct ProbeCache {
+    dir: PathBuf,
+    entries: RwLock<Option<HashMap<String, String>>>,
+}
+
+impl ProbeCache {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            entries: RwLock::new(None),
+        }
+    }
+
+    /// Look up the entry for `key`, which must include everything the result
+    /// depends on (see [`compiler_identity`]).
+    ///
+    /// Returns `None` if any part of the key is not valid UTF-8, as such
+    /// entries are never stored.
+    pub(crate) fn get(&self, kind: &str, key: &[&OsStr]) -> Option<String> {
+        let key = encode_key(kind, key)?;
+        if let Some(entries) = &*self.entries.read().unwrap() {
+            return entries.get(&key).cloned();
+        }
+
+        let mut entries = self.entries.write().unwrap();
+        entries
+            .get_or_insert_with(|| self.read_entries())
+            .get(&key)
+            .cloned()
+    }
+
+    /// Store `value` for `key`.
+    ///
+    /// Failures are ignored, as the cache only serves to speed things up.
+    pub(crate) fn insert(&self, kind: &str, key: &[&OsStr], value: &str) {
+        let key = match encode_key(kind, key) {
+            Some(key) => key,
+            None => return,
+        };
+        let _lock = match self.lock() {
+            Some(lock) => lock,
+            None => return,
+        };
+
+        // Merge with what other processes stored in the meantime.
+        let mut on_disk = self.read_entries();
+        on_disk.insert(key, value.to_string());
+
+        let mut contents = format!("{HEADER}\n");
+        for (key, value) in &on_disk {
+            let _ = writeln!(contents, "{key}\t{value}");
+        }
+        let tmp = self
+            .dir
+            .join(format!("{CACHE_FILE}.{}.tmp", std::process::id()));
+        if fs::write(&tmp, contents).is_ok() && fs::rename(&tmp, self.dir.join(CACHE_FILE)).is_err()
+        {
+            let _ = fs::remove_file(&tmp);
+        }
+
+        *self.entries.write().unwrap() = Some(on_disk);
+    }
+
+    fn read_entries(&self) -> HashMap<String, String> {
+        let contents = fs::read_to_string(self.dir.join(CACHE_FILE)).unwrap_or_default();
+        let mut lines = contents.lines();
+        if lines.next() != Some(HEADER) {
+            return HashMap::new();
+        }
+        lines
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn lock(&self) -> Option<LockFile> {
+        fs::create_dir_all(&self.dir).ok()?;
+        let path = self.dir.join(LOCK_FILE);
+        let start = SystemTime::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Some(LockFile(path)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    let is_stale = fs::metadata(&path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .map_or(false, |age| age > STALE_LOCK);
+                    if is_stale {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if start.elapsed().map_or(true, |waited| waited > LOCK_TIMEOUT) {
+                        return None;
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+/// Removes the lock file when dropped.
+struct LockFile(PathBuf);
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn encode_key(kind: &str, key: &[&OsStr]) -> Option<String> {
+    let mut encoded = format!("{kind}:");
+    for (i, part) in key.iter().enumerate() {
+        if i > 0 {
+            encoded.push_str("00");
+        }
+        for byte in part.to_str()?.bytes() {
+            let _ = write!(encoded, "{byte:02x}");
+        }
+    }
+    Some(encoded)
+}
+
+/// Identify the compiler binary at `path` (looked up in `search_path` if it is
+/// just a name) by its location, size and modification time, so that cache
+/// entries are invalidated when it is upgraded.
+///
+/// Returns `None` if the compiler could not be found.
+pub(crate) fn compiler_identity(path: &Path, search_path: Option<&OsStr>) -> Option<String> {
+    let resolved = if path.components().count() > 1 {
+        path.to_path_buf()
+    } else {
+        // Same lookup as when spawning the compiler.
+        env::split_paths(search_path?).find_map(|dir| {
+            let candidate = dir.join(path);
+            let with_exe = candidate.with_extension(env::consts::EXE_EXTENSION);
+            [candidate, with_exe]
+                .iter()
+                .find(|candidate| candidate.is_file())
+                .cloned()
+        })?
+    };
+    let metadata = fs::metadata(&resolved).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(format!(
+        "{}|{}|{}.{:09}",
+        resolved.display(),
+        metadata.len(),
+        modified.as_secs(),
+        modified.subsec_nanos()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProbeCache;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn persists_between_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = [OsStr::new("gcc"), OsStr::new("-m64")];
+
+        let cache = ProbeCache::new(dir.path().to_path_buf());
+        assert_eq!(cache.get("flag", &key), None);
+        cache.insert("flag", &key, "1");
+        assert_eq!(cache.get("flag", &key).as_deref(), Some("1"));
+
+        let other = ProbeCache::new(dir.path().to_path_buf());
+        assert_eq!(other.get("flag", &key).as_deref(), Some("1"));
+        assert_eq!(other.get("family", &key), None);
+        other.insert("family", &key, "gnu");
+
+        // New entries are merged with those stored by other processes.
+        cache.insert("flag", &[OsStr::new("cc")], "0");
+        let third = ProbeCache::new(dir.path().to_path_buf());
+        assert_eq!(third.get("family", &key).as_deref(), Some("gnu"));
+        assert_eq!(third.get("flag", &[OsStr::new("cc")]).as_deref(), Some("0"));
+        assert!(!dir.path().join(super::LOCK_FILE).exists());
+    }
+}
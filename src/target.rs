@@ -2,11 +2,17 @@
 //! build scripts (`CARGO_CFG_*`).
 
 mod apple;
+mod defaults;
 mod generated;
 mod llvm;
 mod parser;
+mod spec;
+mod triple;
 
 pub(crate) use parser::TargetInfoParser;
+pub(crate) use spec::is_builtin;
+pub use spec::TargetSpec;
+pub(crate) use triple::TripleMismatch;
 
 /// Information specific to a `rustc` target.
 ///
@@ -39,3 +45,123 @@ pub(crate) struct TargetInfo<'a> {
     /// This is the same as the value of `cfg!(target_abi)`.
     pub abi: &'a str,
 }
+
+/// The code generation defaults of a `rustc` target, which are not exposed
+/// to build scripts.
+///
+/// Compiling C code with these gives it the same baseline as the Rust code,
+/// see [`TargetDefaults::flags`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct TargetDefaults<'a> {
+    /// The CPU that code is generated for, in LLVM's naming, or `""` for
+    /// LLVM's default for the target triple.
+    pub cpu: &'a str,
+    /// The LLVM target features enabled or disabled on top of the CPU, e.g.
+    /// `+v7,+vfp3d16,+thumb2,-neon`.
+    pub features: &'a str,
+    /// The width in bits of the largest atomic integer, or `None` if the
+    /// target doesn't have atomics.
+    pub max_atomic_width: Option<u32>,
+    /// The same as the value of `cfg!(target_pointer_width)`.
+    pub pointer_width: u32,
+}
+
+/// Information about the target of a [`Build`](crate::Build), as returned by
+/// [`Build::get_target_info`](crate::Build::get_target_info).
+///
+/// The fields are parsed from `TARGET` and the `CARGO_CFG_TARGET_*`
+/// variables in the same way as for the compiler flags that `cc` adds, so
+/// build scripts don't have to parse the target name themselves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    pub(crate) full_arch: String,
+    pub(crate) arch: String,
+    pub(crate) vendor: String,
+    pub(crate) os: String,
+    pub(crate) env: String,
+    pub(crate) abi: String,
+    pub(crate) llvm_target: String,
+    pub(crate) apple_sdk_name: Option<&'static str>,
+    pub(crate) apple_deployment_target: Option<String>,
+    pub(crate) pointer_width: Option<u32>,
+    pub(crate) max_atomic_width: Option<u32>,
+    pub(crate) is_cross_compile: bool,
+}
+
+impl Target {
+    /// The full architecture, including the subarchitecture, e.g. `armv7`
+    /// or `e2kv4`.
+    ///
+    /// This differs from [`arch`](Self::arch), which is too coarse for
+    /// certain cases.
+    pub fn full_arch(&self) -> &str {
+        &self.full_arch
+    }
+
+    /// The overall target architecture, the same as `cfg!(target_arch)`.
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
+    /// The target vendor, the same as `cfg!(target_vendor)`.
+    pub fn vendor(&self) -> &str {
+        &self.vendor
+    }
+
+    /// The operating system, or `none` on bare-metal targets. The same as
+    /// `cfg!(target_os)`.
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    /// The environment on top of the operating system, the same as
+    /// `cfg!(target_env)`.
+    pub fn env(&self) -> &str {
+        &self.env
+    }
+
+    /// The ABI on top of the operating system, the same as
+    /// `cfg!(target_abi)`.
+    pub fn abi(&self) -> &str {
+        &self.abi
+    }
+
+    /// The LLVM/Clang target triple, as passed to Clang with `--target=`.
+    ///
+    /// See <https://clang.llvm.org/docs/CrossCompilation.html#target-triple>.
+    pub fn llvm_target(&self) -> &str {
+        &self.llvm_target
+    }
+
+    /// The name of the Apple SDK for the target, e.g. `iphonesimulator`, as
+    /// passed to `xcrun --sdk`. `None` if the target is not an Apple one.
+    pub fn apple_sdk_name(&self) -> Option<&str> {
+        self.apple_sdk_name
+    }
+
+    /// The deployment target, i.e. the minimum OS version, e.g. `10.12` on
+    /// macOS. `None` if the target is not an Apple one.
+    ///
+    /// This takes `MACOSX_DEPLOYMENT_TARGET` and friends into account.
+    pub fn apple_deployment_target(&self) -> Option<&str> {
+        self.apple_deployment_target.as_deref()
+    }
+
+    /// The pointer width in bits, the same as `cfg!(target_pointer_width)`.
+    /// `None` if the target is not known to `cc`.
+    pub fn pointer_width(&self) -> Option<u32> {
+        self.pointer_width
+    }
+
+    /// The width in bits of the largest atomic integer, the same as the
+    /// largest `cfg!(target_has_atomic)`. `None` if the target doesn't have
+    /// atomics, or is not known to `cc`.
+    pub fn max_atomic_width(&self) -> Option<u32> {
+        self.max_atomic_width
+    }
+
+    /// Whether the target differs from the host.
+    pub fn is_cross_compile(&self) -> bool {
+        self.is_cross_compile
+    }
+}
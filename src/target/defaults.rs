@@ -0,0 +1,398 @@
+//! Translation of the code generation defaults of `rustc` targets to the
+//! flags of GCC-like compilers.
+
+use super::{generated, TargetDefaults, TargetInfo};
+
+impl TargetDefaults<'static> {
+    /// The defaults of a target that is built into `rustc`.
+    pub(crate) fn from_rustc_target(target: &str) -> Option<Self> {
+        let index = generated::TARGET_DEFAULTS
+            .binary_search_by_key(&target, |(rustc_target, _)| rustc_target)
+            .ok()?;
+        Some(generated::TARGET_DEFAULTS[index].1)
+    }
+}
+
+impl TargetDefaults<'_> {
+    /// The features, and whether each is enabled (`+`) or disabled (`-`).
+    fn features(&self) -> impl Iterator<Item = (bool, &str)> {
+        self.features.split(',').filter_map(|feature| {
+            match (feature.strip_prefix('+'), feature.strip_prefix('-')) {
+                (Some(name), _) => Some((true, name)),
+                (_, Some(name)) => Some((false, name)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Whether the feature is enabled. Later features override earlier ones.
+    fn has_feature(&self, name: &str) -> bool {
+        self.features()
+            .filter(|(_, feature)| *feature == name)
+            .last()
+            .map_or(false, |(enabled, _)| enabled)
+    }
+
+    /// Whether the CPU is LLVM's default for the target triple, which the
+    /// compiler also defaults to.
+    fn has_generic_cpu(&self) -> bool {
+        self.cpu.is_empty() || self.cpu.starts_with("generic") || self.cpu == "x86-64"
+    }
+
+    /// The flags that make a GCC-like compiler generate code for the same
+    /// baseline as `rustc`: the same instruction set, FPU and float ABI.
+    ///
+    /// x86, ARM and RISC-V features are translated to their GCC spelling.
+    /// On other architectures, only `soft-float` and, where GCC uses the same
+    /// names as LLVM, the CPU are passed on.
+    pub(crate) fn flags(&self, target: &TargetInfo<'_>) -> Vec<String> {
+        let mut flags = Vec::new();
+        match target.arch {
+            "x86" | "x86_64" => self.x86_flags(&mut flags),
+            "arm" => self.arm_flags(target, &mut flags),
+            "riscv32" | "riscv64" => self.riscv_flags(&mut flags),
+            arch => {
+                if !self.has_generic_cpu() {
+                    // GCC's CPU names on these architectures are LLVM's,
+                    // and s390x and MIPS take them with `-march`.
+                    match arch {
+                        "aarch64" | "arm64ec" | "csky" | "sparc" | "sparc64" => {
+                            flags.push(format!("-mcpu={}", self.cpu));
+                        }
+                        "s390x" | "mips" | "mips64" | "mips32r6" | "mips64r6" => {
+                            flags.push(format!("-march={}", self.cpu));
+                        }
+                        _ => {}
+                    }
+                }
+                for (enabled, name) in self.features() {
+                    if name == "soft-float" {
+                        flags.push(feature_flag(enabled, name));
+                    }
+                }
+            }
+        }
+        flags
+    }
+
+    fn x86_flags(&self, flags: &mut Vec<String>) {
+        if !self.has_generic_cpu() {
+            flags.push(format!("-march={}", self.cpu));
+        }
+        for (enabled, name) in self.features() {
+            let name = match name {
+                "cmpxchg16b" => "cx16",
+                "lahfsahf" => "sahf",
+                "pclmulqdq" => "pclmul",
+                "rdrand" => "rdrnd",
+                // LVI hardening is only available in Clang, through
+                // `-mlvi-hardening`, which is handled by the SGX target.
+                name if name.starts_with("lvi-") => continue,
+                name => name,
+            };
+            flags.push(feature_flag(enabled, name));
+        }
+    }
+
+    fn arm_flags(&self, target: &TargetInfo<'_>, flags: &mut Vec<String>) {
+        let (thumb, version) = match target.full_arch.strip_prefix("thumb") {
+            Some(version) => (true, version),
+            None => (
+                self.has_feature("thumb-mode"),
+                target.full_arch.strip_prefix("arm").unwrap_or_default(),
+            ),
+        };
+        let (big_endian, version) = match version.strip_prefix("eb") {
+            Some(version) => (true, version),
+            None => (false, version),
+        };
+        let mut version = version.strip_suffix("neon").unwrap_or(version);
+        // `arm` and `armeb` only have the version in the features.
+        if version.is_empty() {
+            version = self
+                .features()
+                .filter(|(enabled, name)| {
+                    *enabled && name.starts_with('v') && name[1..].starts_with(char::is_numeric)
+                })
+                .map(|(_, name)| name)
+                .last()
+                .unwrap_or_default();
+        }
+
+        if let Some(march) = arm_march(version) {
+            flags.push(format!("-march={march}"));
+        }
+        if !self.has_generic_cpu() {
+            flags.push(format!("-mcpu={}", self.cpu));
+        }
+        if big_endian {
+            flags.push("-mbig-endian".into());
+        }
+        flags.push(if thumb { "-mthumb" } else { "-marm" }.into());
+
+        let mut vfp = None;
+        let mut neon = false;
+        let mut d32 = true;
+        let mut soft_float = false;
+        for (enabled, name) in self.features() {
+            match name {
+                "vfp2" | "vfp3" | "vfp3d16" | "vfp4" | "vfp4d16sp" | "fp-armv8"
+                | "fp-armv8d16sp" => vfp = if enabled { Some(name) } else { None },
+                "neon" => neon = enabled,
+                "d32" => d32 = enabled,
+                "soft-float" => soft_float = enabled,
+                _ => {}
+            }
+        }
+        let fpu = arm_fpu(vfp, neon, d32);
+
+        let float_abi = if target.abi == "eabihf" {
+            "hard"
+        } else if soft_float || fpu.is_none() {
+            "soft"
+        } else {
+            // Use the FPU, but pass floats in integer registers.
+            "softfp"
+        };
+        if float_abi != "soft" {
+            if let Some(fpu) = fpu {
+                flags.push(format!("-mfpu={fpu}"));
+            }
+        }
+        flags.push(format!("-mfloat-abi={float_abi}"));
+    }
+
+    fn riscv_flags(&self, flags: &mut Vec<String>) {
+        if !self.has_generic_cpu() {
+            flags.push(format!("-mcpu={}", self.cpu));
+        }
+
+        let xlen = self.pointer_width;
+        let abi = if xlen == 64 { "lp64" } else { "ilp32" };
+        let profile = self
+            .features()
+            .filter(|(enabled, name)| {
+                *enabled && (name.starts_with("rva") || name.starts_with("rvb"))
+            })
+            .map(|(_, name)| name)
+            .last();
+        if let Some(profile) = profile {
+            // All application profiles include the D extension.
+            flags.push(format!("-march={profile}"));
+            flags.push(format!("-mabi={abi}d"));
+            return;
+        }
+
+        let embedded = self.has_feature("e");
+        // `g` is short for `imafd_zicsr_zifencei`. Older versions of GCC
+        // don't know `zicsr` and `zifencei` by name, so prefer the former.
+        let general = !embedded
+            && ["m", "a", "f", "d", "zicsr", "zifencei"]
+                .iter()
+                .all(|name| self.has_feature(name));
+        let mut march = format!("rv{xlen}");
+        let letters = if general {
+            march.push('g');
+            "qlcbkjtpvh"
+        } else {
+            march.push(if embedded { 'e' } else { 'i' });
+            "mafdqlcbkjtpvh"
+        };
+        for letter in letters.chars() {
+            if self.has_feature(letter.encode_utf8(&mut [0; 4])) {
+                march.push(letter);
+            }
+        }
+        for (enabled, name) in self.features() {
+            let multi_letter = name.len() > 1 && name.starts_with(['z', 's', 'x']);
+            if enabled && multi_letter && !(general && (name == "zicsr" || name == "zifencei")) {
+                march.push('_');
+                march.push_str(name);
+            }
+        }
+        flags.push(format!("-march={march}"));
+
+        let float = if embedded {
+            "e"
+        } else if self.has_feature("d") {
+            "d"
+        } else if self.has_feature("f") {
+            "f"
+        } else {
+            ""
+        };
+        flags.push(format!("-mabi={abi}{float}"));
+    }
+}
+
+fn feature_flag(enabled: bool, name: &str) -> String {
+    if enabled {
+        format!("-m{name}")
+    } else {
+        format!("-mno-{name}")
+    }
+}
+
+/// GCC's name for the ARM architecture version in a target name, e.g. `v7em`.
+fn arm_march(version: &str) -> Option<&'static str> {
+    Some(match version {
+        "v4t" => "armv4t",
+        "v5te" => "armv5te",
+        "v6" => "armv6",
+        "v6k" => "armv6k",
+        "v6m" => "armv6s-m",
+        "v7" | "v7a" => "armv7-a",
+        "v7r" => "armv7-r",
+        "v7m" => "armv7-m",
+        "v7em" => "armv7e-m",
+        "v8" | "v8a" => "armv8-a",
+        "v8r" => "armv8-r",
+        "v8m.base" => "armv8-m.base",
+        "v8m.main" => "armv8-m.main",
+        _ => return None,
+    })
+}
+
+/// GCC's name for the FPU selected by LLVM's VFP and NEON features.
+fn arm_fpu(vfp: Option<&str>, neon: bool, d32: bool) -> Option<&'static str> {
+    Some(match (vfp, neon) {
+        (Some("vfp4"), true) => "neon-vfpv4",
+        (Some("fp-armv8"), true) => "neon-fp-armv8",
+        (_, true) => "neon",
+        (Some("vfp2"), false) => "vfpv2",
+        (Some("vfp3"), false) if d32 => "vfpv3",
+        (Some("vfp3" | "vfp3d16"), false) => "vfpv3-d16",
+        (Some("vfp4"), false) if d32 => "vfpv4",
+        (Some("vfp4"), false) => "vfpv4-d16",
+        (Some("vfp4d16sp"), false) => "fpv4-sp-d16",
+        (Some("fp-armv8"), false) if d32 => "fp-armv8",
+        (Some("fp-armv8"), false) => "fpv5-d16",
+        (Some("fp-armv8d16sp"), false) => "fpv5-sp-d16",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TargetDefaults, TargetInfo};
+
+    fn flags(target: &str) -> Vec<String> {
+        let info = TargetInfo::from_rustc_target(target).unwrap();
+        let defaults = TargetDefaults::from_rustc_target(target).unwrap();
+        defaults.flags(&info)
+    }
+
+    #[test]
+    fn x86() {
+        assert_eq!(flags("i586-unknown-linux-gnu"), ["-march=pentium"]);
+        assert_eq!(flags("i686-unknown-linux-gnu"), ["-march=pentium4"]);
+        assert_eq!(
+            flags("x86_64-unknown-uefi"),
+            ["-mno-mmx", "-mno-sse", "-msoft-float"]
+        );
+    }
+
+    #[test]
+    fn arm() {
+        assert_eq!(
+            flags("armv7-unknown-linux-gnueabihf"),
+            [
+                "-march=armv7-a",
+                "-marm",
+                "-mfpu=vfpv3-d16",
+                "-mfloat-abi=hard"
+            ]
+        );
+        assert_eq!(
+            flags("armv7-linux-androideabi"),
+            [
+                "-march=armv7-a",
+                "-mthumb",
+                "-mfpu=vfpv3-d16",
+                "-mfloat-abi=softfp"
+            ]
+        );
+        assert_eq!(
+            flags("arm-unknown-linux-gnueabihf"),
+            ["-march=armv6", "-marm", "-mfpu=vfpv2", "-mfloat-abi=hard"]
+        );
+        assert_eq!(
+            flags("arm-linux-androideabi"),
+            ["-march=armv5te", "-marm", "-mfloat-abi=soft"]
+        );
+        assert_eq!(
+            flags("thumbv7neon-unknown-linux-gnueabihf"),
+            [
+                "-march=armv7-a",
+                "-mthumb",
+                "-mfpu=neon",
+                "-mfloat-abi=hard"
+            ]
+        );
+        assert_eq!(
+            flags("thumbv7em-none-eabihf"),
+            [
+                "-march=armv7e-m",
+                "-mthumb",
+                "-mfpu=fpv4-sp-d16",
+                "-mfloat-abi=hard"
+            ]
+        );
+        assert_eq!(
+            flags("armebv7r-none-eabi"),
+            [
+                "-march=armv7-r",
+                "-mbig-endian",
+                "-marm",
+                "-mfloat-abi=soft"
+            ]
+        );
+        assert_eq!(
+            flags("armv6k-nintendo-3ds"),
+            [
+                "-march=armv6k",
+                "-mcpu=mpcore",
+                "-marm",
+                "-mfpu=vfpv2",
+                "-mfloat-abi=hard"
+            ]
+        );
+    }
+
+    #[test]
+    fn riscv() {
+        assert_eq!(
+            flags("riscv64gc-unknown-linux-gnu"),
+            ["-march=rv64gc", "-mabi=lp64d"]
+        );
+        assert_eq!(
+            flags("riscv64-linux-android"),
+            ["-march=rv64gcbv", "-mabi=lp64d"]
+        );
+        assert_eq!(
+            flags("riscv32imafc-unknown-none-elf"),
+            ["-march=rv32imafc", "-mabi=ilp32f"]
+        );
+        assert_eq!(
+            flags("riscv32emc-unknown-none-elf"),
+            ["-march=rv32emc", "-mabi=ilp32e"]
+        );
+        assert_eq!(
+            flags("riscv64gc-unknown-redox"),
+            ["-march=rv64imafdc", "-mabi=lp64d"]
+        );
+        assert_eq!(
+            flags("riscv64a23-unknown-linux-gnu"),
+            ["-march=rva23u64", "-mabi=lp64d"]
+        );
+    }
+
+    #[test]
+    fn other() {
+        assert_eq!(flags("s390x-unknown-linux-gnu"), ["-march=z10"]);
+        assert_eq!(flags("mips64-unknown-linux-gnuabi64"), ["-march=mips64r2"]);
+        assert_eq!(flags("sparc64-unknown-linux-gnu"), ["-mcpu=v9"]);
+        assert_eq!(flags("powerpc64-unknown-linux-gnu"), Vec::<String>::new());
+        assert_eq!(flags("aarch64-unknown-linux-gnu"), Vec::<String>::new());
+    }
+}
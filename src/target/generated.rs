@@ -0,0 +1,705 @@
+//! This file is generated code. Please edit the generator in
+//! dev-tools/gen-target-info if you need to make changes, or see
+//! src/target/llvm.rs if you need to configure a specific LLVM triple.
+
+use super::TargetDefaults;
+
+#[rustfmt::skip]
+pub(crate) const LLVM_TARGETS: &[(&str, &str)] = &[
+    ("aarch64-apple-darwin", "arm64-apple-macosx"),
+    ("aarch64-apple-ios", "arm64-apple-ios"),
+    ("aarch64-apple-ios-macabi", "arm64-apple-ios-macabi"),
+    ("aarch64-apple-ios-sim", "arm64-apple-ios-simulator"),
+    ("aarch64-apple-tvos", "arm64-apple-tvos"),
+    ("aarch64-apple-tvos-sim", "arm64-apple-tvos-simulator"),
+    ("aarch64-apple-visionos", "arm64-apple-xros"),
+    ("aarch64-apple-visionos-sim", "arm64-apple-xros-simulator"),
+    ("aarch64-apple-watchos", "arm64-apple-watchos"),
+    ("aarch64-apple-watchos-sim", "arm64-apple-watchos-simulator"),
+    ("aarch64-fuchsia", "aarch64-fuchsia"),
+    ("aarch64-kmc-solid_asp3", "aarch64-unknown-none"),
+    ("aarch64-linux-android", "aarch64-linux-android"),
+    ("aarch64-nintendo-switch-freestanding", "aarch64-unknown-none"),
+    ("aarch64-pc-windows-gnullvm", "aarch64-pc-windows-gnu"),
+    ("aarch64-pc-windows-msvc", "aarch64-pc-windows-msvc"),
+    ("aarch64-unknown-freebsd", "aarch64-unknown-freebsd"),
+    ("aarch64-unknown-fuchsia", "aarch64-unknown-fuchsia"),
+    ("aarch64-unknown-helenos", "aarch64-unknown-helenos"),
+    ("aarch64-unknown-hermit", "aarch64-unknown-hermit"),
+    ("aarch64-unknown-illumos", "aarch64-unknown-solaris2.11"),
+    ("aarch64-unknown-linux-gnu", "aarch64-unknown-linux-gnu"),
+    ("aarch64-unknown-linux-gnu_ilp32", "aarch64-unknown-linux-gnu_ilp32"),
+    ("aarch64-unknown-linux-musl", "aarch64-unknown-linux-musl"),
+    ("aarch64-unknown-linux-ohos", "aarch64-unknown-linux-ohos"),
+    ("aarch64-unknown-managarm-mlibc", "aarch64-unknown-managarm-mlibc"),
+    ("aarch64-unknown-netbsd", "aarch64-unknown-netbsd"),
+    ("aarch64-unknown-none", "aarch64-unknown-none"),
+    ("aarch64-unknown-none-softfloat", "aarch64-unknown-none"),
+    ("aarch64-unknown-nto-qnx700", "aarch64-unknown-unknown"),
+    ("aarch64-unknown-nto-qnx710", "aarch64-unknown-unknown"),
+    ("aarch64-unknown-nto-qnx710_iosock", "aarch64-unknown-unknown"),
+    ("aarch64-unknown-nto-qnx800", "aarch64-unknown-unknown"),
+    ("aarch64-unknown-nuttx", "aarch64-unknown-none"),
+    ("aarch64-unknown-openbsd", "aarch64-unknown-openbsd"),
+    ("aarch64-unknown-redox", "aarch64-unknown-redox"),
+    ("aarch64-unknown-teeos", "aarch64-unknown-none"),
+    ("aarch64-unknown-trusty", "aarch64-unknown-unknown-musl"),
+    ("aarch64-unknown-uefi", "aarch64-unknown-windows"),
+    ("aarch64-uwp-windows-msvc", "aarch64-pc-windows-msvc"),
+    ("aarch64-wrs-vxworks", "aarch64-unknown-linux-gnu"),
+    ("aarch64_be-unknown-hermit", "aarch64_be-unknown-hermit"),
+    ("aarch64_be-unknown-linux-gnu", "aarch64_be-unknown-linux-gnu"),
+    ("aarch64_be-unknown-linux-gnu_ilp32", "aarch64_be-unknown-linux-gnu_ilp32"),
+    ("aarch64_be-unknown-linux-musl", "aarch64_be-unknown-linux-musl"),
+    ("aarch64_be-unknown-netbsd", "aarch64_be-unknown-netbsd"),
+    ("aarch64_be-unknown-none-softfloat", "aarch64_be-unknown-none"),
+    ("aarch64v8r-unknown-none", "aarch64-unknown-none"),
+    ("aarch64v8r-unknown-none-softfloat", "aarch64-unknown-none"),
+    ("amdgcn-amd-amdhsa", "amdgcn-amd-amdhsa"),
+    ("arm-linux-androideabi", "arm-linux-androideabi"),
+    ("arm-unknown-linux-gnueabi", "arm-unknown-linux-gnueabi"),
+    ("arm-unknown-linux-gnueabihf", "arm-unknown-linux-gnueabihf"),
+    ("arm-unknown-linux-musleabi", "arm-unknown-linux-musleabi"),
+    ("arm-unknown-linux-musleabihf", "arm-unknown-linux-musleabihf"),
+    ("arm64_32-apple-watchos", "arm64_32-apple-watchos"),
+    ("arm64e-apple-darwin", "arm64e-apple-macosx"),
+    ("arm64e-apple-ios", "arm64e-apple-ios"),
+    ("arm64e-apple-tvos", "arm64e-apple-tvos"),
+    ("arm64ec-pc-windows-msvc", "arm64ec-pc-windows-msvc"),
+    ("armeb-unknown-linux-gnueabi", "armeb-unknown-linux-gnueabi"),
+    ("armebv7r-none-eabi", "armebv7r-none-eabi"),
+    ("armebv7r-none-eabihf", "armebv7r-none-eabihf"),
+    ("armv4t-none-eabi", "armv4t-none-eabi"),
+    ("armv4t-unknown-linux-gnueabi", "armv4t-unknown-linux-gnueabi"),
+    ("armv5te-none-eabi", "armv5te-none-eabi"),
+    ("armv5te-unknown-linux-gnueabi", "armv5te-unknown-linux-gnueabi"),
+    ("armv5te-unknown-linux-musleabi", "armv5te-unknown-linux-musleabi"),
+    ("armv5te-unknown-linux-uclibceabi", "armv5te-unknown-linux-gnueabi"),
+    ("armv6-none-eabi", "armv6-none-eabi"),
+    ("armv6-none-eabihf", "armv6-none-eabihf"),
+    ("armv6-unknown-freebsd", "armv6-unknown-freebsd-gnueabihf"),
+    ("armv6-unknown-netbsd-eabihf", "armv6-unknown-netbsdelf-eabihf"),
+    ("armv6k-nintendo-3ds", "armv6k-none-eabihf"),
+    ("armv7-apple-ios", "armv7-apple-ios7.0.0"),
+    ("armv7-linux-androideabi", "armv7-none-linux-android"),
+    ("armv7-rtems-eabihf", "armv7-unknown-none-eabihf"),
+    ("armv7-sony-vita-newlibeabihf", "thumbv7a-sony-vita-eabihf"),
+    ("armv7-unknown-freebsd", "armv7-unknown-freebsd-gnueabihf"),
+    ("armv7-unknown-linux-gnueabi", "armv7-unknown-linux-gnueabi"),
+    ("armv7-unknown-linux-gnueabihf", "armv7-unknown-linux-gnueabihf"),
+    ("armv7-unknown-linux-musleabi", "armv7-unknown-linux-musleabi"),
+    ("armv7-unknown-linux-musleabihf", "armv7-unknown-linux-musleabihf"),
+    ("armv7-unknown-linux-ohos", "armv7-unknown-linux-ohos"),
+    ("armv7-unknown-linux-uclibceabi", "armv7-unknown-linux-gnueabi"),
+    ("armv7-unknown-linux-uclibceabihf", "armv7-unknown-linux-gnueabihf"),
+    ("armv7-unknown-netbsd-eabihf", "armv7-unknown-netbsdelf-eabihf"),
+    ("armv7-unknown-trusty", "armv7-unknown-unknown-gnueabi"),
+    ("armv7-wrs-vxworks-eabihf", "armv7-unknown-linux-gnueabihf"),
+    ("armv7a-kmc-solid_asp3-eabi", "armv7a-none-eabi"),
+    ("armv7a-kmc-solid_asp3-eabihf", "armv7a-none-eabihf"),
+    ("armv7a-none-eabi", "armv7a-none-eabi"),
+    ("armv7a-none-eabihf", "armv7a-none-eabihf"),
+    ("armv7a-nuttx-eabi", "armv7a-none-eabi"),
+    ("armv7a-nuttx-eabihf", "armv7a-none-eabihf"),
+    ("armv7a-vex-v5", "armv7a-none-eabihf"),
+    ("armv7k-apple-watchos", "armv7k-apple-watchos"),
+    ("armv7r-none-eabi", "armv7r-none-eabi"),
+    ("armv7r-none-eabihf", "armv7r-none-eabihf"),
+    ("armv7s-apple-ios", "armv7s-apple-ios"),
+    ("armv8r-none-eabihf", "armv8r-none-eabihf"),
+    ("asmjs-unknown-emscripten", "wasm32-unknown-emscripten"),
+    ("avr-none", "avr-unknown-unknown"),
+    ("avr-unknown-gnu-atmega328", "avr-unknown-unknown"),
+    ("bpfeb-unknown-none", "bpfeb"),
+    ("bpfel-unknown-none", "bpfel"),
+    ("csky-unknown-linux-gnuabiv2", "csky-unknown-linux-gnuabiv2"),
+    ("csky-unknown-linux-gnuabiv2hf", "csky-unknown-linux-gnuabiv2"),
+    ("e2k-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k12c-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k16c-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k1cplus-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k2c3-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k48c-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k4c-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k8c-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k8c2-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2k8v7-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2kv3-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2kv4-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2kv5-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2kv6-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("e2kv7-unknown-linux-gnu", "e2k-unknown-linux-gnu"),
+    ("hexagon-unknown-linux-musl", "hexagon-unknown-linux-musl"),
+    ("hexagon-unknown-none-elf", "hexagon-unknown-none-elf"),
+    ("hexagon-unknown-qurt", "hexagon-unknown-elf"),
+    ("i386-apple-ios", "i386-apple-ios-simulator"),
+    ("i586-pc-windows-msvc", "i586-pc-windows-msvc"),
+    ("i586-unknown-linux-gnu", "i586-unknown-linux-gnu"),
+    ("i586-unknown-linux-musl", "i586-unknown-linux-musl"),
+    ("i586-unknown-netbsd", "i586-unknown-netbsdelf"),
+    ("i586-unknown-redox", "i586-unknown-redox"),
+    ("i686-apple-darwin", "i686-apple-macosx"),
+    ("i686-linux-android", "i686-linux-android"),
+    ("i686-pc-nto-qnx700", "i586-pc-unknown"),
+    ("i686-pc-windows-gnu", "i686-pc-windows-gnu"),
+    ("i686-pc-windows-gnullvm", "i686-pc-windows-gnu"),
+    ("i686-pc-windows-msvc", "i686-pc-windows-msvc"),
+    ("i686-unknown-freebsd", "i686-unknown-freebsd"),
+    ("i686-unknown-haiku", "i686-unknown-haiku"),
+    ("i686-unknown-helenos", "i686-unknown-helenos"),
+    ("i686-unknown-hurd-gnu", "i686-unknown-hurd-gnu"),
+    ("i686-unknown-linux-gnu", "i686-unknown-linux-gnu"),
+    ("i686-unknown-linux-musl", "i686-unknown-linux-musl"),
+    ("i686-unknown-netbsd", "i686-unknown-netbsdelf"),
+    ("i686-unknown-openbsd", "i686-unknown-openbsd"),
+    ("i686-unknown-uefi", "i686-unknown-windows-gnu"),
+    ("i686-uwp-windows-gnu", "i686-pc-windows-gnu"),
+    ("i686-uwp-windows-msvc", "i686-pc-windows-msvc"),
+    ("i686-win7-windows-gnu", "i686-pc-windows-gnu"),
+    ("i686-win7-windows-msvc", "i686-pc-windows-msvc"),
+    ("i686-wrs-vxworks", "i686-unknown-linux-gnu"),
+    ("loongarch32-unknown-none", "loongarch32-unknown-none"),
+    ("loongarch32-unknown-none-softfloat", "loongarch32-unknown-none"),
+    ("loongarch64-unknown-linux-gnu", "loongarch64-unknown-linux-gnu"),
+    ("loongarch64-unknown-linux-musl", "loongarch64-unknown-linux-musl"),
+    ("loongarch64-unknown-linux-ohos", "loongarch64-unknown-linux-ohos"),
+    ("loongarch64-unknown-none", "loongarch64-unknown-none"),
+    ("loongarch64-unknown-none-softfloat", "loongarch64-unknown-none"),
+    ("m68k-unknown-linux-gnu", "m68k-unknown-linux-gnu"),
+    ("m68k-unknown-none-elf", "m68k"),
+    ("mips-mti-none-elf", "mips"),
+    ("mips-unknown-linux-gnu", "mips-unknown-linux-gnu"),
+    ("mips-unknown-linux-musl", "mips-unknown-linux-musl"),
+    ("mips-unknown-linux-uclibc", "mips-unknown-linux-gnu"),
+    ("mips64-openwrt-linux-musl", "mips64-unknown-linux-musl"),
+    ("mips64-unknown-linux-gnuabi64", "mips64-unknown-linux-gnuabi64"),
+    ("mips64-unknown-linux-muslabi64", "mips64-unknown-linux-musl"),
+    ("mips64el-unknown-linux-gnuabi64", "mips64el-unknown-linux-gnuabi64"),
+    ("mips64el-unknown-linux-muslabi64", "mips64el-unknown-linux-musl"),
+    ("mipsel-mti-none-elf", "mipsel"),
+    ("mipsel-sony-psp", "mipsel-sony-psp"),
+    ("mipsel-sony-psx", "mipsel-sony-psx"),
+    ("mipsel-unknown-linux-gnu", "mipsel-unknown-linux-gnu"),
+    ("mipsel-unknown-linux-musl", "mipsel-unknown-linux-musl"),
+    ("mipsel-unknown-linux-uclibc", "mipsel-unknown-linux-gnu"),
+    ("mipsel-unknown-netbsd", "mipsel-unknown-netbsd"),
+    ("mipsel-unknown-none", "mipsel-unknown-none"),
+    ("mipsisa32r6-unknown-linux-gnu", "mipsisa32r6-unknown-linux-gnu"),
+    ("mipsisa32r6el-unknown-linux-gnu", "mipsisa32r6el-unknown-linux-gnu"),
+    ("mipsisa64r6-unknown-linux-gnuabi64", "mipsisa64r6-unknown-linux-gnuabi64"),
+    ("mipsisa64r6el-unknown-linux-gnuabi64", "mipsisa64r6el-unknown-linux-gnuabi64"),
+    ("msp430-none-elf", "msp430-none-elf"),
+    ("nvptx64-nvidia-cuda", "nvptx64-nvidia-cuda"),
+    ("powerpc-unknown-freebsd", "powerpc-unknown-freebsd13.0"),
+    ("powerpc-unknown-helenos", "powerpc-unknown-helenos"),
+    ("powerpc-unknown-linux-gnu", "powerpc-unknown-linux-gnu"),
+    ("powerpc-unknown-linux-gnuspe", "powerpc-unknown-linux-gnuspe"),
+    ("powerpc-unknown-linux-musl", "powerpc-unknown-linux-musl"),
+    ("powerpc-unknown-linux-muslspe", "powerpc-unknown-linux-muslspe"),
+    ("powerpc-unknown-netbsd", "powerpc-unknown-netbsd"),
+    ("powerpc-unknown-openbsd", "powerpc-unknown-openbsd"),
+    ("powerpc-wrs-vxworks", "powerpc-unknown-linux-gnu"),
+    ("powerpc-wrs-vxworks-spe", "powerpc-unknown-linux-gnuspe"),
+    ("powerpc64-ibm-aix", "powerpc64-ibm-aix"),
+    ("powerpc64-unknown-freebsd", "powerpc64-unknown-freebsd"),
+    ("powerpc64-unknown-linux-gnu", "powerpc64-unknown-linux-gnu"),
+    ("powerpc64-unknown-linux-musl", "powerpc64-unknown-linux-musl"),
+    ("powerpc64-unknown-openbsd", "powerpc64-unknown-openbsd"),
+    ("powerpc64-wrs-vxworks", "powerpc64-unknown-linux-gnu"),
+    ("powerpc64le-unknown-freebsd", "powerpc64le-unknown-freebsd"),
+    ("powerpc64le-unknown-linux-gnu", "powerpc64le-unknown-linux-gnu"),
+    ("powerpc64le-unknown-linux-musl", "powerpc64le-unknown-linux-musl"),
+    ("riscv32-wrs-vxworks", "riscv32-unknown-linux-gnu"),
+    ("riscv32e-unknown-none-elf", "riscv32"),
+    ("riscv32em-unknown-none-elf", "riscv32"),
+    ("riscv32emc-unknown-none-elf", "riscv32"),
+    ("riscv32gc-unknown-linux-gnu", "riscv32-unknown-linux-gnu"),
+    ("riscv32gc-unknown-linux-musl", "riscv32-unknown-linux-musl"),
+    ("riscv32i-unknown-none-elf", "riscv32"),
+    ("riscv32im-risc0-zkvm-elf", "riscv32"),
+    ("riscv32im-unknown-none-elf", "riscv32"),
+    ("riscv32ima-unknown-none-elf", "riscv32"),
+    ("riscv32imac-esp-espidf", "riscv32"),
+    ("riscv32imac-unknown-none-elf", "riscv32"),
+    ("riscv32imac-unknown-nuttx-elf", "riscv32"),
+    ("riscv32imac-unknown-xous-elf", "riscv32"),
+    ("riscv32imafc-esp-espidf", "riscv32"),
+    ("riscv32imafc-unknown-none-elf", "riscv32"),
+    ("riscv32imafc-unknown-nuttx-elf", "riscv32"),
+    ("riscv32imc-esp-espidf", "riscv32"),
+    ("riscv32imc-unknown-none-elf", "riscv32"),
+    ("riscv32imc-unknown-nuttx-elf", "riscv32"),
+    ("riscv64-linux-android", "riscv64-linux-android"),
+    ("riscv64-wrs-vxworks", "riscv64-unknown-linux-gnu"),
+    ("riscv64a23-unknown-linux-gnu", "riscv64-unknown-linux-gnu"),
+    ("riscv64gc-unknown-freebsd", "riscv64-unknown-freebsd"),
+    ("riscv64gc-unknown-fuchsia", "riscv64-unknown-fuchsia"),
+    ("riscv64gc-unknown-hermit", "riscv64-unknown-hermit"),
+    ("riscv64gc-unknown-linux-gnu", "riscv64-unknown-linux-gnu"),
+    ("riscv64gc-unknown-linux-musl", "riscv64-unknown-linux-musl"),
+    ("riscv64gc-unknown-managarm-mlibc", "riscv64-unknown-managarm-mlibc"),
+    ("riscv64gc-unknown-netbsd", "riscv64-unknown-netbsd"),
+    ("riscv64gc-unknown-none-elf", "riscv64"),
+    ("riscv64gc-unknown-nuttx-elf", "riscv64"),
+    ("riscv64gc-unknown-openbsd", "riscv64-unknown-openbsd"),
+    ("riscv64gc-unknown-redox", "riscv64-unknown-redox"),
+    ("riscv64im-unknown-none-elf", "riscv64"),
+    ("riscv64imac-unknown-none-elf", "riscv64"),
+    ("riscv64imac-unknown-nuttx-elf", "riscv64"),
+    ("s390x-unknown-linux-gnu", "s390x-unknown-linux-gnu"),
+    ("s390x-unknown-linux-musl", "s390x-unknown-linux-musl"),
+    ("s390x-unknown-none-softfloat", "s390x-unknown-linux-gnu"),
+    ("sparc-unknown-linux-gnu", "sparc-unknown-linux-gnu"),
+    ("sparc-unknown-none-elf", "sparc-unknown-none-elf"),
+    ("sparc64-unknown-helenos", "sparc64-unknown-helenos"),
+    ("sparc64-unknown-linux-gnu", "sparc64-unknown-linux-gnu"),
+    ("sparc64-unknown-netbsd", "sparc64-unknown-netbsd"),
+    ("sparc64-unknown-openbsd", "sparc64-unknown-openbsd"),
+    ("sparcv9-sun-solaris", "sparcv9-sun-solaris"),
+    ("thumbv4t-none-eabi", "thumbv4t-none-eabi"),
+    ("thumbv5te-none-eabi", "thumbv5te-none-eabi"),
+    ("thumbv6-none-eabi", "thumbv6-none-eabi"),
+    ("thumbv6m-none-eabi", "thumbv6m-none-eabi"),
+    ("thumbv6m-nuttx-eabi", "thumbv6m-none-eabi"),
+    ("thumbv7a-none-eabi", "thumbv7a-none-eabi"),
+    ("thumbv7a-none-eabihf", "thumbv7a-none-eabihf"),
+    ("thumbv7a-nuttx-eabi", "thumbv7a-none-eabi"),
+    ("thumbv7a-nuttx-eabihf", "thumbv7a-none-eabihf"),
+    ("thumbv7a-pc-windows-msvc", "thumbv7a-pc-windows-msvc"),
+    ("thumbv7a-uwp-windows-msvc", "thumbv7a-pc-windows-msvc"),
+    ("thumbv7em-none-eabi", "thumbv7em-none-eabi"),
+    ("thumbv7em-none-eabihf", "thumbv7em-none-eabihf"),
+    ("thumbv7em-nuttx-eabi", "thumbv7em-none-eabi"),
+    ("thumbv7em-nuttx-eabihf", "thumbv7em-none-eabihf"),
+    ("thumbv7m-none-eabi", "thumbv7m-none-eabi"),
+    ("thumbv7m-nuttx-eabi", "thumbv7m-none-eabi"),
+    ("thumbv7neon-linux-androideabi", "armv7-none-linux-android"),
+    ("thumbv7neon-unknown-linux-gnueabihf", "armv7-unknown-linux-gnueabihf"),
+    ("thumbv7neon-unknown-linux-musleabihf", "armv7-unknown-linux-musleabihf"),
+    ("thumbv7r-none-eabi", "thumbv7r-none-eabi"),
+    ("thumbv7r-none-eabihf", "thumbv7r-none-eabihf"),
+    ("thumbv8m.base-none-eabi", "thumbv8m.base-none-eabi"),
+    ("thumbv8m.base-nuttx-eabi", "thumbv8m.base-none-eabi"),
+    ("thumbv8m.main-none-eabi", "thumbv8m.main-none-eabi"),
+    ("thumbv8m.main-none-eabihf", "thumbv8m.main-none-eabihf"),
+    ("thumbv8m.main-nuttx-eabi", "thumbv8m.main-none-eabi"),
+    ("thumbv8m.main-nuttx-eabihf", "thumbv8m.main-none-eabihf"),
+    ("thumbv8r-none-eabihf", "thumbv8r-none-eabihf"),
+    ("wasm32-unknown-emscripten", "wasm32-unknown-emscripten"),
+    ("wasm32-unknown-unknown", "wasm32-unknown-unknown"),
+    ("wasm32-wali-linux-musl", "wasm32-linux-muslwali"),
+    ("wasm32-wasi", "wasm32-wasi"),
+    ("wasm32-wasip1", "wasm32-wasip1"),
+    ("wasm32-wasip1-threads", "wasm32-wasi"),
+    ("wasm32-wasip2", "wasm32-wasip2"),
+    ("wasm32-wasip3", "wasm32-wasip3"),
+    ("wasm32v1-none", "wasm32-unknown-unknown"),
+    ("wasm64-unknown-unknown", "wasm64-unknown-unknown"),
+    ("x86_64-apple-darwin", "x86_64-apple-macosx"),
+    ("x86_64-apple-ios", "x86_64-apple-ios-simulator"),
+    ("x86_64-apple-ios-macabi", "x86_64-apple-ios-macabi"),
+    ("x86_64-apple-tvos", "x86_64-apple-tvos-simulator"),
+    ("x86_64-apple-watchos-sim", "x86_64-apple-watchos-simulator"),
+    ("x86_64-fortanix-unknown-sgx", "x86_64-elf"),
+    ("x86_64-fuchsia", "x86_64-fuchsia"),
+    ("x86_64-linux-android", "x86_64-linux-android"),
+    ("x86_64-lynx-lynxos178", "x86_64-unknown-unknown-gnu"),
+    ("x86_64-pc-cygwin", "x86_64-pc-cygwin"),
+    ("x86_64-pc-nto-qnx710", "x86_64-pc-unknown"),
+    ("x86_64-pc-nto-qnx710_iosock", "x86_64-pc-unknown"),
+    ("x86_64-pc-nto-qnx800", "x86_64-pc-unknown"),
+    ("x86_64-pc-solaris", "x86_64-pc-solaris"),
+    ("x86_64-pc-windows-gnu", "x86_64-pc-windows-gnu"),
+    ("x86_64-pc-windows-gnullvm", "x86_64-pc-windows-gnu"),
+    ("x86_64-pc-windows-msvc", "x86_64-pc-windows-msvc"),
+    ("x86_64-sun-solaris", "x86_64-pc-solaris"),
+    ("x86_64-unikraft-linux-musl", "x86_64-unknown-linux-musl"),
+    ("x86_64-unknown-dragonfly", "x86_64-unknown-dragonfly"),
+    ("x86_64-unknown-freebsd", "x86_64-unknown-freebsd"),
+    ("x86_64-unknown-fuchsia", "x86_64-unknown-fuchsia"),
+    ("x86_64-unknown-haiku", "x86_64-unknown-haiku"),
+    ("x86_64-unknown-helenos", "x86_64-unknown-helenos"),
+    ("x86_64-unknown-hermit", "x86_64-unknown-hermit"),
+    ("x86_64-unknown-hurd-gnu", "x86_64-unknown-hurd-gnu"),
+    ("x86_64-unknown-illumos", "x86_64-pc-solaris"),
+    ("x86_64-unknown-l4re-uclibc", "x86_64-unknown-l4re-gnu"),
+    ("x86_64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"),
+    ("x86_64-unknown-linux-gnuasan", "x86_64-unknown-linux-gnu"),
+    ("x86_64-unknown-linux-gnumsan", "x86_64-unknown-linux-gnu"),
+    ("x86_64-unknown-linux-gnutsan", "x86_64-unknown-linux-gnu"),
+    ("x86_64-unknown-linux-gnux32", "x86_64-unknown-linux-gnux32"),
+    ("x86_64-unknown-linux-musl", "x86_64-unknown-linux-musl"),
+    ("x86_64-unknown-linux-none", "x86_64-unknown-linux-none"),
+    ("x86_64-unknown-linux-ohos", "x86_64-unknown-linux-ohos"),
+    ("x86_64-unknown-managarm-mlibc", "x86_64-unknown-managarm-mlibc"),
+    ("x86_64-unknown-motor", "x86_64-unknown-none-elf"),
+    ("x86_64-unknown-netbsd", "x86_64-unknown-netbsd"),
+    ("x86_64-unknown-none", "x86_64-unknown-none-elf"),
+    ("x86_64-unknown-none-linuxkernel", "x86_64-unknown-none-elf"),
+    ("x86_64-unknown-openbsd", "x86_64-unknown-openbsd"),
+    ("x86_64-unknown-redox", "x86_64-unknown-redox"),
+    ("x86_64-unknown-trusty", "x86_64-unknown-unknown-musl"),
+    ("x86_64-unknown-uefi", "x86_64-unknown-windows"),
+    ("x86_64-uwp-windows-gnu", "x86_64-pc-windows-gnu"),
+    ("x86_64-uwp-windows-msvc", "x86_64-pc-windows-msvc"),
+    ("x86_64-win7-windows-gnu", "x86_64-pc-windows-gnu"),
+    ("x86_64-win7-windows-msvc", "x86_64-pc-windows-msvc"),
+    ("x86_64-wrs-vxworks", "x86_64-unknown-linux-gnu"),
+    ("x86_64h-apple-darwin", "x86_64h-apple-macosx"),
+    ("xtensa-esp32-espidf", "xtensa-none-elf"),
+    ("xtensa-esp32-none-elf", "xtensa-none-elf"),
+    ("xtensa-esp32s2-espidf", "xtensa-none-elf"),
+    ("xtensa-esp32s2-none-elf", "xtensa-none-elf"),
+    ("xtensa-esp32s3-espidf", "xtensa-none-elf"),
+    ("xtensa-esp32s3-none-elf", "xtensa-none-elf"),
+];
+
+#[rustfmt::skip]
+pub(crate) const TARGET_DEFAULTS: &[(&str, TargetDefaults<'static>)] = &[
+    ("aarch64-apple-darwin", TargetDefaults { cpu: "apple-m1", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-ios", TargetDefaults { cpu: "apple-a7", features: "+neon,+apple-a7", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-ios-macabi", TargetDefaults { cpu: "apple-a12", features: "+neon,+apple-a12", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-ios-sim", TargetDefaults { cpu: "apple-a12", features: "+neon,+apple-a7", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-tvos", TargetDefaults { cpu: "apple-a7", features: "+neon,+apple-a7", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-tvos-sim", TargetDefaults { cpu: "apple-a12", features: "+neon,+apple-a7", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-visionos", TargetDefaults { cpu: "apple-a7", features: "+neon,+apple-a16", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-visionos-sim", TargetDefaults { cpu: "apple-a12", features: "+neon,+apple-a16", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-watchos", TargetDefaults { cpu: "apple-a7", features: "+v8a,+neon,+apple-a7", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-apple-watchos-sim", TargetDefaults { cpu: "apple-a12", features: "+neon,+apple-a7", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-fuchsia", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-kmc-solid_asp3", TargetDefaults { cpu: "", features: "+v8a,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-linux-android", TargetDefaults { cpu: "", features: "+v8a,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-nintendo-switch-freestanding", TargetDefaults { cpu: "", features: "+v8a,+neon,+crypto,+crc", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-pc-windows-gnullvm", TargetDefaults { cpu: "", features: "+v8a,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-pc-windows-msvc", TargetDefaults { cpu: "", features: "+v8a,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-freebsd", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-fuchsia", TargetDefaults { cpu: "", features: "+v8a,+crc,+aes,+sha2,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-helenos", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-hermit", TargetDefaults { cpu: "", features: "+v8a,+strict-align,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-illumos", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-linux-gnu", TargetDefaults { cpu: "", features: "+v8a,+outline-atomics", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-linux-gnu_ilp32", TargetDefaults { cpu: "", features: "+v8a,+outline-atomics", max_atomic_width: Some(128), pointer_width: 32 }),
+    ("aarch64-unknown-linux-musl", TargetDefaults { cpu: "", features: "+v8a,+outline-atomics", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-linux-ohos", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-managarm-mlibc", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-netbsd", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-none", TargetDefaults { cpu: "", features: "+v8a,+strict-align,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-none-softfloat", TargetDefaults { cpu: "", features: "+v8a,+strict-align,-neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-nto-qnx700", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-nto-qnx710", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-nto-qnx710_iosock", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-nto-qnx800", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-nuttx", TargetDefaults { cpu: "", features: "+v8a,+strict-align,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-openbsd", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-redox", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-teeos", TargetDefaults { cpu: "", features: "+strict-align,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-trusty", TargetDefaults { cpu: "", features: "+neon,+reserve-x18", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-unknown-uefi", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-uwp-windows-msvc", TargetDefaults { cpu: "", features: "+v8a", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64-wrs-vxworks", TargetDefaults { cpu: "", features: "+v8a,+reserve-x18", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64_be-unknown-hermit", TargetDefaults { cpu: "", features: "+v8a,+strict-align,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64_be-unknown-linux-gnu", TargetDefaults { cpu: "", features: "+v8a,+outline-atomics", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64_be-unknown-linux-gnu_ilp32", TargetDefaults { cpu: "", features: "+v8a,+outline-atomics", max_atomic_width: Some(128), pointer_width: 32 }),
+    ("aarch64_be-unknown-linux-musl", TargetDefaults { cpu: "", features: "+v8a,+outline-atomics", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64_be-unknown-netbsd", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64_be-unknown-none-softfloat", TargetDefaults { cpu: "", features: "+v8a,+strict-align,-neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64v8r-unknown-none", TargetDefaults { cpu: "", features: "+v8r,+strict-align", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("aarch64v8r-unknown-none-softfloat", TargetDefaults { cpu: "", features: "+v8r,+strict-align,-neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("amdgcn-amd-amdhsa", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("arm-linux-androideabi", TargetDefaults { cpu: "", features: "+strict-align,+v5te", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("arm-unknown-linux-gnueabi", TargetDefaults { cpu: "", features: "+strict-align,+v6", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("arm-unknown-linux-gnueabihf", TargetDefaults { cpu: "", features: "+strict-align,+v6,+vfp2", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("arm-unknown-linux-musleabi", TargetDefaults { cpu: "", features: "+strict-align,+v6", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("arm-unknown-linux-musleabihf", TargetDefaults { cpu: "", features: "+strict-align,+v6,+vfp2", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("arm64_32-apple-watchos", TargetDefaults { cpu: "apple-s4", features: "+v8a,+neon,+apple-a7", max_atomic_width: Some(128), pointer_width: 32 }),
+    ("arm64e-apple-darwin", TargetDefaults { cpu: "apple-m1", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("arm64e-apple-ios", TargetDefaults { cpu: "apple-a12", features: "+neon,+apple-a12,+v8.3a,+paca,+pacg", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("arm64e-apple-tvos", TargetDefaults { cpu: "apple-a12", features: "+neon,+apple-a12,+v8.3a,+paca,+pacg", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("arm64ec-pc-windows-msvc", TargetDefaults { cpu: "", features: "+v8a,+neon", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("armeb-unknown-linux-gnueabi", TargetDefaults { cpu: "", features: "+strict-align,+v8,+crc", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armebv7r-none-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armebv7r-none-eabihf", TargetDefaults { cpu: "", features: "+vfp3d16", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv4t-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(0), pointer_width: 32 }),
+    ("armv4t-unknown-linux-gnueabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("armv5te-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(0), pointer_width: 32 }),
+    ("armv5te-unknown-linux-gnueabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("armv5te-unknown-linux-musleabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("armv5te-unknown-linux-uclibceabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("armv6-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align,+v6k", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv6-none-eabihf", TargetDefaults { cpu: "", features: "+strict-align,+v6k,+vfp2,-d32", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv6-unknown-freebsd", TargetDefaults { cpu: "", features: "+v6,+vfp2", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv6-unknown-netbsd-eabihf", TargetDefaults { cpu: "", features: "+v6,+vfp2", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv6k-nintendo-3ds", TargetDefaults { cpu: "mpcore", features: "+vfp2", max_atomic_width: None, pointer_width: 32 }),
+    ("armv7-apple-ios", TargetDefaults { cpu: "cortex-a8", features: "+v7,+vfp3,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-linux-androideabi", TargetDefaults { cpu: "", features: "+v7,+thumb-mode,+thumb2,+vfp3d16,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-rtems-eabihf", TargetDefaults { cpu: "", features: "+thumb2,+neon,+vfp3", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-sony-vita-newlibeabihf", TargetDefaults { cpu: "cortex-a9", features: "+v7,+neon,+vfp3,+thumb2,+thumb-mode", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-freebsd", TargetDefaults { cpu: "", features: "+v7,+vfp3d16,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-linux-gnueabi", TargetDefaults { cpu: "", features: "+v7,+thumb2,+soft-float,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-linux-gnueabihf", TargetDefaults { cpu: "", features: "+v7,+vfp3d16,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-linux-musleabi", TargetDefaults { cpu: "", features: "+v7,+thumb2,+soft-float,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-linux-musleabihf", TargetDefaults { cpu: "", features: "+v7,+vfp3d16,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-linux-ohos", TargetDefaults { cpu: "", features: "+v7,+thumb2,+soft-float,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-linux-uclibceabi", TargetDefaults { cpu: "", features: "+v7,+thumb2,+soft-float,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-linux-uclibceabihf", TargetDefaults { cpu: "", features: "+v7,+vfp3d16,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-netbsd-eabihf", TargetDefaults { cpu: "", features: "+v7,+vfp3d16,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-unknown-trusty", TargetDefaults { cpu: "", features: "+v7,+thumb2,+soft-float,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7-wrs-vxworks-eabihf", TargetDefaults { cpu: "", features: "+v7,+vfp3d16,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7a-kmc-solid_asp3-eabi", TargetDefaults { cpu: "", features: "+v7,+soft-float,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7a-kmc-solid_asp3-eabihf", TargetDefaults { cpu: "", features: "+v7,+vfp3d16,+thumb2,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7a-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,-neon,+strict-align", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7a-none-eabihf", TargetDefaults { cpu: "", features: "+vfp3d16,-neon,+strict-align", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7a-nuttx-eabi", TargetDefaults { cpu: "", features: "+v7,+thumb2,+soft-float,-neon,+strict-align", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7a-nuttx-eabihf", TargetDefaults { cpu: "", features: "+v7,+thumb2,+vfp3,+neon,+strict-align", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7a-vex-v5", TargetDefaults { cpu: "cortex-a9", features: "+v7,+neon,+vfp3d16,+thumb2", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7k-apple-watchos", TargetDefaults { cpu: "cortex-a8", features: "+v7,+vfp4,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7r-none-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7r-none-eabihf", TargetDefaults { cpu: "", features: "+vfp3d16", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv7s-apple-ios", TargetDefaults { cpu: "swift", features: "+v7,+vfp4,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("armv8r-none-eabihf", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("asmjs-unknown-emscripten", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("avr-none", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(16), pointer_width: 16 }),
+    ("avr-unknown-gnu-atmega328", TargetDefaults { cpu: "atmega328", features: "", max_atomic_width: Some(0), pointer_width: 16 }),
+    ("bpfeb-unknown-none", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("bpfel-unknown-none", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("csky-unknown-linux-gnuabiv2", TargetDefaults { cpu: "", features: "+2e3,+3e7,+7e10,+cache,+dsp1e2,+dspe60,+e1,+e2,+edsp,+elrw,+hard-tp,+high-registers,+hwdiv,+mp,+mp1e2,+nvic,+trust", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("csky-unknown-linux-gnuabiv2hf", TargetDefaults { cpu: "ck860fv", features: "+hard-float,+hard-float-abi,+2e3,+3e7,+7e10,+cache,+dsp1e2,+dspe60,+e1,+e2,+edsp,+elrw,+hard-tp,+high-registers,+hwdiv,+mp,+mp1e2,+nvic,+trust", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("e2k-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k12c-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k16c-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k1cplus-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k2c3-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k48c-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k4c-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k8c-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k8c2-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2k8v7-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2kv3-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2kv4-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2kv5-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2kv6-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("e2kv7-unknown-linux-gnu", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("hexagon-unknown-linux-musl", TargetDefaults { cpu: "hexagonv60", features: "-small-data,+hvx-length128b", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("hexagon-unknown-none-elf", TargetDefaults { cpu: "hexagonv60", features: "-small-data,+hvx-length128b", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("hexagon-unknown-qurt", TargetDefaults { cpu: "hexagonv69", features: "-small-data,+hvx-length128b", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("i386-apple-ios", TargetDefaults { cpu: "penryn", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i586-pc-windows-msvc", TargetDefaults { cpu: "pentium", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i586-unknown-linux-gnu", TargetDefaults { cpu: "pentium", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i586-unknown-linux-musl", TargetDefaults { cpu: "pentium", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i586-unknown-netbsd", TargetDefaults { cpu: "pentium", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i586-unknown-redox", TargetDefaults { cpu: "pentiumpro", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-apple-darwin", TargetDefaults { cpu: "penryn", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-linux-android", TargetDefaults { cpu: "pentium4", features: "+mmx,+sse,+sse2,+sse3,+ssse3", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-pc-nto-qnx700", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-pc-windows-gnu", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-pc-windows-gnullvm", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-pc-windows-msvc", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-freebsd", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-haiku", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-helenos", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-hurd-gnu", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-linux-gnu", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-linux-musl", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-netbsd", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-openbsd", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-unknown-uefi", TargetDefaults { cpu: "pentium4", features: "-mmx,-sse,+soft-float", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-uwp-windows-gnu", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-uwp-windows-msvc", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-win7-windows-gnu", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-win7-windows-msvc", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("i686-wrs-vxworks", TargetDefaults { cpu: "pentium4", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("loongarch32-unknown-none", TargetDefaults { cpu: "", features: "+f,+d", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("loongarch32-unknown-none-softfloat", TargetDefaults { cpu: "", features: "-f,-d", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("loongarch64-unknown-linux-gnu", TargetDefaults { cpu: "", features: "+f,+d,+lsx,+relax", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("loongarch64-unknown-linux-musl", TargetDefaults { cpu: "", features: "+f,+d,+lsx,+relax", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("loongarch64-unknown-linux-ohos", TargetDefaults { cpu: "", features: "+f,+d,+lsx,+relax", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("loongarch64-unknown-none", TargetDefaults { cpu: "", features: "+f,+d,-lsx", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("loongarch64-unknown-none-softfloat", TargetDefaults { cpu: "", features: "-f,-d", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("m68k-unknown-linux-gnu", TargetDefaults { cpu: "M68020", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("m68k-unknown-none-elf", TargetDefaults { cpu: "M68010", features: "", max_atomic_width: None, pointer_width: 32 }),
+    ("mips-mti-none-elf", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+soft-float,+noabicalls", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mips-unknown-linux-gnu", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+fpxx,+nooddspreg", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mips-unknown-linux-musl", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+soft-float", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mips-unknown-linux-uclibc", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+soft-float", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mips64-openwrt-linux-musl", TargetDefaults { cpu: "mips64r2", features: "+mips64r2,+soft-float", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("mips64-unknown-linux-gnuabi64", TargetDefaults { cpu: "mips64r2", features: "+mips64r2,+xgot", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("mips64-unknown-linux-muslabi64", TargetDefaults { cpu: "mips64r2", features: "+mips64r2,+xgot", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("mips64el-unknown-linux-gnuabi64", TargetDefaults { cpu: "mips64r2", features: "+mips64r2,+xgot", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("mips64el-unknown-linux-muslabi64", TargetDefaults { cpu: "mips64r2", features: "+mips64r2,+xgot", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("mipsel-mti-none-elf", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+soft-float,+noabicalls", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsel-sony-psp", TargetDefaults { cpu: "mips2", features: "+single-float", max_atomic_width: None, pointer_width: 32 }),
+    ("mipsel-sony-psx", TargetDefaults { cpu: "mips1", features: "+soft-float", max_atomic_width: Some(0), pointer_width: 32 }),
+    ("mipsel-unknown-linux-gnu", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+fpxx,+nooddspreg", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsel-unknown-linux-musl", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+soft-float", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsel-unknown-linux-uclibc", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+soft-float", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsel-unknown-netbsd", TargetDefaults { cpu: "mips32", features: "+soft-float", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsel-unknown-none", TargetDefaults { cpu: "mips32r2", features: "+mips32r2,+soft-float,+noabicalls", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsisa32r6-unknown-linux-gnu", TargetDefaults { cpu: "mips32r6", features: "+mips32r6", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsisa32r6el-unknown-linux-gnu", TargetDefaults { cpu: "mips32r6", features: "+mips32r6", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("mipsisa64r6-unknown-linux-gnuabi64", TargetDefaults { cpu: "mips64r6", features: "+mips64r6", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("mipsisa64r6el-unknown-linux-gnuabi64", TargetDefaults { cpu: "mips64r6", features: "+mips64r6", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("msp430-none-elf", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(0), pointer_width: 16 }),
+    ("nvptx64-nvidia-cuda", TargetDefaults { cpu: "sm_70", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc-unknown-freebsd", TargetDefaults { cpu: "", features: "+secure-plt", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-unknown-helenos", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-unknown-linux-gnu", TargetDefaults { cpu: "", features: "+secure-plt", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-unknown-linux-gnuspe", TargetDefaults { cpu: "", features: "+secure-plt,+msync", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-unknown-linux-musl", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-unknown-linux-muslspe", TargetDefaults { cpu: "", features: "+msync", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-unknown-netbsd", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-unknown-openbsd", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-wrs-vxworks", TargetDefaults { cpu: "", features: "+secure-plt", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc-wrs-vxworks-spe", TargetDefaults { cpu: "", features: "+secure-plt,+msync", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("powerpc64-ibm-aix", TargetDefaults { cpu: "pwr7", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64-unknown-freebsd", TargetDefaults { cpu: "ppc64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64-unknown-linux-gnu", TargetDefaults { cpu: "ppc64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64-unknown-linux-musl", TargetDefaults { cpu: "ppc64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64-unknown-openbsd", TargetDefaults { cpu: "ppc64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64-wrs-vxworks", TargetDefaults { cpu: "ppc64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64le-unknown-freebsd", TargetDefaults { cpu: "ppc64le", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64le-unknown-linux-gnu", TargetDefaults { cpu: "ppc64le", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("powerpc64le-unknown-linux-musl", TargetDefaults { cpu: "ppc64le", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv32-wrs-vxworks", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32e-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+e,+forced-atomics", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32em-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+e,+m,+forced-atomics", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32emc-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+e,+m,+c,+forced-atomics", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32gc-unknown-linux-gnu", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32gc-unknown-linux-musl", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32i-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+forced-atomics", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32im-risc0-zkvm-elf", TargetDefaults { cpu: "generic-rv32", features: "+m", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("riscv32im-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+forced-atomics", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32ima-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imac-esp-espidf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+c", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imac-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+c", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imac-unknown-nuttx-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+c", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imac-unknown-xous-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+c", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imafc-esp-espidf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+c,+f", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imafc-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+c,+f", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imafc-unknown-nuttx-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+a,+c,+f", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imc-esp-espidf", TargetDefaults { cpu: "generic-rv32", features: "+m,+c", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imc-unknown-none-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+c,+forced-atomics", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv32imc-unknown-nuttx-elf", TargetDefaults { cpu: "generic-rv32", features: "+m,+c", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("riscv64-linux-android", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+b,+v,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64-wrs-vxworks", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64a23-unknown-linux-gnu", TargetDefaults { cpu: "generic-rv64", features: "+rva23u64", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-freebsd", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-fuchsia", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+v,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-hermit", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-linux-gnu", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-linux-musl", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-managarm-mlibc", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-netbsd", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-none-elf", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-nuttx-elf", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-openbsd", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c,+zicsr,+zifencei", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64gc-unknown-redox", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+f,+d,+c", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64im-unknown-none-elf", TargetDefaults { cpu: "generic-rv64", features: "+m,+forced-atomics", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64imac-unknown-none-elf", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+c", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("riscv64imac-unknown-nuttx-elf", TargetDefaults { cpu: "generic-rv64", features: "+m,+a,+c", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("s390x-unknown-linux-gnu", TargetDefaults { cpu: "z10", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("s390x-unknown-linux-musl", TargetDefaults { cpu: "z10", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("s390x-unknown-none-softfloat", TargetDefaults { cpu: "z10", features: "+soft-float,-vector", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("sparc-unknown-linux-gnu", TargetDefaults { cpu: "v9", features: "+v8plus", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("sparc-unknown-none-elf", TargetDefaults { cpu: "v7", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("sparc64-unknown-helenos", TargetDefaults { cpu: "v9", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("sparc64-unknown-linux-gnu", TargetDefaults { cpu: "v9", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("sparc64-unknown-netbsd", TargetDefaults { cpu: "v9", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("sparc64-unknown-openbsd", TargetDefaults { cpu: "v9", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("sparcv9-sun-solaris", TargetDefaults { cpu: "v9", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("thumbv4t-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(0), pointer_width: 32 }),
+    ("thumbv5te-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align", max_atomic_width: Some(0), pointer_width: 32 }),
+    ("thumbv6-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,+strict-align,+v6k", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv6m-none-eabi", TargetDefaults { cpu: "", features: "+strict-align,+atomics-32", max_atomic_width: None, pointer_width: 32 }),
+    ("thumbv6m-nuttx-eabi", TargetDefaults { cpu: "", features: "+strict-align", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv7a-none-eabi", TargetDefaults { cpu: "", features: "+soft-float,-neon,+strict-align", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7a-none-eabihf", TargetDefaults { cpu: "", features: "+vfp3d16,-neon,+strict-align", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7a-nuttx-eabi", TargetDefaults { cpu: "", features: "+soft-float,-neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7a-nuttx-eabihf", TargetDefaults { cpu: "", features: "+vfp3,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7a-pc-windows-msvc", TargetDefaults { cpu: "", features: "+vfp3,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7a-uwp-windows-msvc", TargetDefaults { cpu: "", features: "+vfp3,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7em-none-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv7em-none-eabihf", TargetDefaults { cpu: "", features: "+vfp4d16sp", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv7em-nuttx-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv7em-nuttx-eabihf", TargetDefaults { cpu: "", features: "+vfp4d16sp", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv7m-none-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv7m-nuttx-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv7neon-linux-androideabi", TargetDefaults { cpu: "", features: "+v7,+thumb-mode,+thumb2,+vfp3,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7neon-unknown-linux-gnueabihf", TargetDefaults { cpu: "", features: "+v7,+thumb-mode,+thumb2,+vfp3,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7neon-unknown-linux-musleabihf", TargetDefaults { cpu: "", features: "+v7,+thumb-mode,+thumb2,+vfp3,+neon", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7r-none-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv7r-none-eabihf", TargetDefaults { cpu: "", features: "+vfp3d16", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("thumbv8m.base-none-eabi", TargetDefaults { cpu: "", features: "+strict-align", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv8m.base-nuttx-eabi", TargetDefaults { cpu: "", features: "+strict-align", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv8m.main-none-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv8m.main-none-eabihf", TargetDefaults { cpu: "", features: "+fp-armv8d16sp", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv8m.main-nuttx-eabi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv8m.main-nuttx-eabihf", TargetDefaults { cpu: "", features: "+fp-armv8d16sp", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("thumbv8r-none-eabihf", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-unknown-emscripten", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-unknown-unknown", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-wali-linux-musl", TargetDefaults { cpu: "", features: "+atomics,+bulk-memory,+mutable-globals,+sign-ext", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-wasi", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-wasip1", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-wasip1-threads", TargetDefaults { cpu: "", features: "+atomics,+bulk-memory,+mutable-globals", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-wasip2", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32-wasip3", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm32v1-none", TargetDefaults { cpu: "mvp", features: "+mutable-globals", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("wasm64-unknown-unknown", TargetDefaults { cpu: "", features: "+bulk-memory,+mutable-globals,+sign-ext,+nontrapping-fptoint", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-apple-darwin", TargetDefaults { cpu: "penryn", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-apple-ios", TargetDefaults { cpu: "penryn", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-apple-ios-macabi", TargetDefaults { cpu: "penryn", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-apple-tvos", TargetDefaults { cpu: "penryn", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-apple-watchos-sim", TargetDefaults { cpu: "penryn", features: "", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-fortanix-unknown-sgx", TargetDefaults { cpu: "x86-64", features: "+rdrand,+rdseed,+lvi-cfi,+lvi-load-hardening", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-fuchsia", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-linux-android", TargetDefaults { cpu: "x86-64", features: "+mmx,+sse,+sse2,+sse3,+ssse3,+sse4.1,+sse4.2,+popcnt", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-lynx-lynxos178", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-pc-cygwin", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-pc-nto-qnx710", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-pc-nto-qnx710_iosock", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-pc-nto-qnx800", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-pc-solaris", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-pc-windows-gnu", TargetDefaults { cpu: "x86-64", features: "+cmpxchg16b,+sse3,+lahfsahf", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-pc-windows-gnullvm", TargetDefaults { cpu: "x86-64", features: "+cmpxchg16b,+sse3,+lahfsahf", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-pc-windows-msvc", TargetDefaults { cpu: "x86-64", features: "+cmpxchg16b,+sse3,+lahfsahf", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-sun-solaris", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unikraft-linux-musl", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-dragonfly", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-freebsd", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-fuchsia", TargetDefaults { cpu: "x86-64", features: "+cmpxchg16b,+lahfsahf,+popcnt,+sse3,+sse4.1,+sse4.2,+ssse3", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-unknown-haiku", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-helenos", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-hermit", TargetDefaults { cpu: "x86-64", features: "+rdrand,+rdseed", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-hurd-gnu", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-illumos", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-l4re-uclibc", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-linux-gnu", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-linux-gnuasan", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-linux-gnumsan", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-linux-gnutsan", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-linux-gnux32", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 32 }),
+    ("x86_64-unknown-linux-musl", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-linux-none", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-linux-ohos", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-managarm-mlibc", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-motor", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-netbsd", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-none", TargetDefaults { cpu: "x86-64", features: "-mmx,-sse,-sse2,-sse3,-ssse3,-sse4.1,-sse4.2,-avx,-avx2,+soft-float", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-none-linuxkernel", TargetDefaults { cpu: "x86-64", features: "-mmx,-sse,-sse2,-sse3,-ssse3,-sse4.1,-sse4.2,-3dnow,-3dnowa,-avx,-avx2,+soft-float", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-openbsd", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-redox", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-trusty", TargetDefaults { cpu: "", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-unknown-uefi", TargetDefaults { cpu: "x86-64", features: "-mmx,-sse,+soft-float", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-uwp-windows-gnu", TargetDefaults { cpu: "x86-64", features: "+cmpxchg16b,+sse3,+lahfsahf", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-uwp-windows-msvc", TargetDefaults { cpu: "x86-64", features: "+cmpxchg16b,+sse3,+lahfsahf", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("x86_64-win7-windows-gnu", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-win7-windows-msvc", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64-wrs-vxworks", TargetDefaults { cpu: "x86-64", features: "", max_atomic_width: Some(64), pointer_width: 64 }),
+    ("x86_64h-apple-darwin", TargetDefaults { cpu: "core-avx2", features: "-rdrand,-aes,-pclmulqdq,-rtm,-fsgsbase", max_atomic_width: Some(128), pointer_width: 64 }),
+    ("xtensa-esp32-espidf", TargetDefaults { cpu: "esp32", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("xtensa-esp32-none-elf", TargetDefaults { cpu: "esp32", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("xtensa-esp32s2-espidf", TargetDefaults { cpu: "esp32s2", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("xtensa-esp32s2-none-elf", TargetDefaults { cpu: "esp32s2", features: "+forced-atomics", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("xtensa-esp32s3-espidf", TargetDefaults { cpu: "esp32s3", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+    ("xtensa-esp32s3-none-elf", TargetDefaults { cpu: "esp32s3", features: "", max_atomic_width: Some(32), pointer_width: 32 }),
+];
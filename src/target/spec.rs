@@ -0,0 +1,303 @@
+//! Custom target specifications, as read from a target JSON file or
+//! registered with [`Build::register_target`](crate::Build::register_target).
+
+use std::fs;
+use std::path::Path;
+
+use super::{generated, TargetDefaults, TargetInfo};
+use crate::{json, Error, ErrorKind};
+
+/// Whether `rustc` knows the target `name` without a target spec file.
+pub(crate) fn is_builtin(name: &str) -> bool {
+    generated::LLVM_TARGETS
+        .binary_search_by_key(&name, |(rustc_target, _)| rustc_target)
+        .is_ok()
+}
+
+/// The parts of a `rustc` target specification that `cc` needs to compile C
+/// code for a target that it doesn't know.
+///
+/// `rustc` reads custom targets from JSON files, see
+/// <https://doc.rust-lang.org/rustc/targets/custom.html>. `cc` reads such a
+/// file when `TARGET` is a path to one, or when the target isn't built into
+/// `rustc` and a `<target>.json` file exists in one of the directories in
+/// `RUST_TARGET_PATH`. Other targets can be described to `cc` with
+/// [`Build::register_target`](crate::Build::register_target).
+///
+/// # Example
+///
+/// ```no_run
+/// let mut spec = cc::TargetSpec::new("riscv64-unknown-elf", "riscv64");
+/// spec.cpu("sifive-u74").features("+m,+a,+c");
+///
+/// cc::Build::new()
+///     .register_target("riscv64gc-acme-elf", spec)
+///     .file("src/foo.c")
+///     .compile("foo");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetSpec {
+    /// The first component of the target name, set when the spec is used.
+    pub(crate) full_arch: String,
+    pub(crate) llvm_target: String,
+    pub(crate) arch: String,
+    pub(crate) vendor: String,
+    pub(crate) os: String,
+    pub(crate) env: String,
+    pub(crate) abi: String,
+    pub(crate) cpu: Option<String>,
+    pub(crate) features: String,
+    pub(crate) max_atomic_width: Option<u32>,
+    pub(crate) pointer_width: u32,
+}
+
+impl TargetSpec {
+    /// Create a target specification from the fields that `rustc` requires:
+    /// the LLVM target triple, passed to Clang with `--target=`, and the
+    /// architecture, as in `cfg!(target_arch)`.
+    ///
+    /// The other fields default to the same values as in `rustc`.
+    pub fn new(llvm_target: &str, arch: &str) -> TargetSpec {
+        TargetSpec {
+            full_arch: arch.into(),
+            llvm_target: llvm_target.into(),
+            arch: arch.into(),
+            vendor: "unknown".into(),
+            os: "none".into(),
+            env: String::new(),
+            abi: String::new(),
+            cpu: None,
+            features: String::new(),
+            max_atomic_width: None,
+            pointer_width: match arch {
+                "avr" | "msp430" => 16,
+                "s390x" => 64,
+                arch if arch.ends_with("64") => 64,
+                _ => 32,
+            },
+        }
+    }
+
+    /// Set the vendor, as in `cfg!(target_vendor)`. Defaults to `unknown`.
+    pub fn vendor(&mut self, vendor: &str) -> &mut TargetSpec {
+        self.vendor = vendor.into();
+        self
+    }
+
+    /// Set the operating system, as in `cfg!(target_os)`. Defaults to
+    /// `none`.
+    pub fn os(&mut self, os: &str) -> &mut TargetSpec {
+        self.os = os.into();
+        self
+    }
+
+    /// Set the environment, as in `cfg!(target_env)`. Defaults to empty.
+    pub fn env(&mut self, env: &str) -> &mut TargetSpec {
+        self.env = env.into();
+        self
+    }
+
+    /// Set the ABI, as in `cfg!(target_abi)`. Defaults to empty.
+    pub fn abi(&mut self, abi: &str) -> &mut TargetSpec {
+        self.abi = abi.into();
+        self
+    }
+
+    /// Set the CPU to compile for, in LLVM's naming, which is passed to the
+    /// compiler with `-mcpu=`, or `-march=` on x86.
+    pub fn cpu(&mut self, cpu: &str) -> &mut TargetSpec {
+        self.cpu = Some(cpu.into());
+        self
+    }
+
+    /// Set the target features, in the same comma-separated form as in
+    /// `rustc`, e.g. `+sse4.2,-avx`.
+    ///
+    /// The features are translated to the compiler's flags on x86, ARM and
+    /// RISC-V, e.g. `+vfp4d16sp` to `-mfpu=fpv4-sp-d16`. Elsewhere, only
+    /// `soft-float` is passed on.
+    pub fn features(&mut self, features: &str) -> &mut TargetSpec {
+        self.features = features.into();
+        self
+    }
+
+    /// Set the width in bits of the largest atomic integer. Defaults to no
+    /// atomics.
+    pub fn max_atomic_width(&mut self, width: u32) -> &mut TargetSpec {
+        self.max_atomic_width = Some(width);
+        self
+    }
+
+    /// Set the pointer width in bits, as in `cfg!(target_pointer_width)`.
+    /// Defaults to 64 on 64-bit architectures, and 32 on most others.
+    pub fn pointer_width(&mut self, width: u32) -> &mut TargetSpec {
+        self.pointer_width = width;
+        self
+    }
+
+    /// Parse the contents of a target JSON file.
+    ///
+    /// Fields that `cc` doesn't use are ignored.
+    pub fn from_json(json: &str) -> Result<TargetSpec, Error> {
+        let invalid = |message: String| Error::new(ErrorKind::InvalidTarget, message);
+        let fields = match json::parse(json).map_err(|e| invalid(format!("invalid JSON: {e}")))? {
+            json::Value::Object(fields) => fields,
+            _ => return Err(invalid("a target spec must be a JSON object".into())),
+        };
+        let field = |key: &str| -> Result<Option<&str>, Error> {
+            match fields.iter().rev().find(|(k, _)| k == key) {
+                Some((_, json::Value::String(value))) => Ok(Some(value)),
+                Some(_) => Err(invalid(format!("`{key}` must be a string"))),
+                None => Ok(None),
+            }
+        };
+        // Older versions of `rustc` wrote numbers as strings.
+        let number = |key: &str| -> Result<Option<u32>, Error> {
+            match fields.iter().rev().find(|(k, _)| k == key) {
+                Some((_, json::Value::String(value) | json::Value::Number(value))) => value
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| invalid(format!("`{key}` must be an integer"))),
+                Some(_) => Err(invalid(format!("`{key}` must be an integer"))),
+                None => Ok(None),
+            }
+        };
+        let required =
+            |key: &str| field(key)?.ok_or_else(|| invalid(format!("missing field `{key}`")));
+
+        let mut spec = TargetSpec::new(required("llvm-target")?, required("arch")?);
+        if let Some(vendor) = field("vendor")? {
+            spec.vendor(vendor);
+        }
+        if let Some(os) = field("os")? {
+            spec.os(os);
+        }
+        if let Some(env) = field("env")? {
+            spec.env(env);
+        }
+        if let Some(abi) = field("abi")? {
+            spec.abi(abi);
+        }
+        if let Some(cpu) = field("cpu")? {
+            spec.cpu(cpu);
+        }
+        if let Some(features) = field("features")? {
+            spec.features(features);
+        }
+        if let Some(width) = number("max-atomic-width")? {
+            spec.max_atomic_width(width);
+        }
+        if let Some(width) = number("target-pointer-width")? {
+            spec.pointer_width(width);
+        }
+        Ok(spec)
+    }
+
+    pub(crate) fn from_file(path: &Path) -> Result<TargetSpec, Error> {
+        let json = fs::read_to_string(path).map_err(|e| {
+            Error::new(
+                ErrorKind::IOError,
+                format!("failed to read target spec {}: {e}", path.display()),
+            )
+        })?;
+        TargetSpec::from_json(&json).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidTarget,
+                format!("invalid target spec {}: {}", path.display(), e.message),
+            )
+        })
+    }
+
+    /// Use the spec for the target `name`.
+    pub(crate) fn with_name(mut self, name: &str) -> TargetSpec {
+        let stem = Path::new(name)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(name);
+        if let Some((full_arch, _)) = stem.split_once('-') {
+            self.full_arch = full_arch.into();
+        }
+        self
+    }
+
+    pub(crate) fn target_info(&self) -> TargetInfo<'_> {
+        TargetInfo {
+            full_arch: &self.full_arch,
+            arch: &self.arch,
+            vendor: &self.vendor,
+            os: &self.os,
+            env: &self.env,
+            abi: &self.abi,
+        }
+    }
+
+    pub(crate) fn defaults(&self) -> TargetDefaults<'_> {
+        TargetDefaults {
+            cpu: self.cpu.as_deref().unwrap_or_default(),
+            features: &self.features,
+            max_atomic_width: self.max_atomic_width,
+            pointer_width: self.pointer_width,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TargetSpec;
+
+    #[test]
+    fn from_json() {
+        let spec = TargetSpec::from_json(
+            r#"{
+                "llvm-target": "x86_64-unknown-none-elf",
+                "arch": "x86_64",
+                "os": "acme",
+                "cpu": "x86-64-v2",
+                "features": "+sse4.2,-avx,+soft-float",
+                "target-pointer-width": "64",
+                "max-atomic-width": 64,
+                "pre-link-args": {"gcc": ["-nostdlib", "-Wl,\"x\\u0041\""]},
+                "executables": true,
+                "metadata": null
+            }"#,
+        )
+        .unwrap()
+        .with_name("x86_64-acme.json");
+        assert_eq!(spec.llvm_target, "x86_64-unknown-none-elf");
+        assert_eq!(spec.full_arch, "x86_64");
+        assert_eq!(spec.vendor, "unknown");
+        assert_eq!(spec.os, "acme");
+        assert_eq!(spec.env, "");
+        assert_eq!(spec.pointer_width, 64);
+        assert_eq!(spec.max_atomic_width, Some(64));
+        assert_eq!(
+            spec.defaults().flags(&spec.target_info()),
+            ["-march=x86-64-v2", "-msse4.2", "-mno-avx", "-msoft-float"]
+        );
+    }
+
+    #[test]
+    fn from_json_errors() {
+        assert!(TargetSpec::from_json(r#"{"arch": "x86_64"}"#).is_err());
+        assert!(TargetSpec::from_json(r#"{"llvm-target": 1, "arch": "x86_64"}"#).is_err());
+        assert!(TargetSpec::from_json(r#"{"llvm-target": "x", "arch": "y""#).is_err());
+        assert!(TargetSpec::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn flags() {
+        let mut spec = TargetSpec::new("riscv64-unknown-elf", "riscv64");
+        spec.cpu("sifive-u74").features("+m,+a,+c");
+        assert_eq!(
+            spec.defaults().flags(&spec.target_info()),
+            ["-mcpu=sifive-u74", "-march=rv64imac", "-mabi=lp64"]
+        );
+
+        let mut spec = TargetSpec::new("powerpc-unknown-eabi", "powerpc");
+        spec.cpu("generic").features("-soft-float");
+        assert_eq!(
+            spec.defaults().flags(&spec.target_info()),
+            ["-mno-soft-float"]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use super::TargetInfo;
+
+/// How the target triple reported by a compiler differs from the `rustc`
+/// target.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum TripleMismatch {
+    /// The compiler produces code for a different architecture. This can't
+    /// be fixed by passing flags, so the objects will certainly be wrong.
+    Arch,
+    /// The compiler targets a different operating system.
+    Os,
+    /// The compiler targets a different environment, e.g. glibc vs. musl.
+    Env,
+}
+
+impl TargetInfo<'_> {
+    /// Compare against the triple reported by a compiler's `-dumpmachine`
+    /// (or similar), e.g. `x86_64-linux-gnu` or `arm-none-eabi`.
+    ///
+    /// Compiler triples are not normalized, so only the parts that can be
+    /// recognized are compared.
+    pub(crate) fn compiler_triple_mismatch(&self, triple: &str) -> Option<TripleMismatch> {
+        let mut components = triple.split('-');
+        let arch = components.next()?;
+        if arch_family(arch) != arch_family(self.arch) {
+            return Some(TripleMismatch::Arch);
+        }
+
+        let rest: Vec<&str> = components.collect();
+        // Bare-metal targets are commonly built with hosted compilers.
+        if self.os != "none" {
+            // `android` is often preceded by `linux`.
+            let os = if rest.iter().any(|c| c.starts_with("android")) {
+                Some("android")
+            } else {
+                rest.iter().find_map(|c| os_from_component(c))
+            };
+            if let Some(os) = os {
+                if os != self.os {
+                    return Some(TripleMismatch::Os);
+                }
+            }
+        }
+        if matches!(self.env, "gnu" | "musl" | "msvc") {
+            if let Some(env) = rest.iter().find_map(|c| env_from_component(c)) {
+                if env != self.env {
+                    return Some(TripleMismatch::Env);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Group architectures between which a single compiler can usually switch
+/// with flags like `-m32`.
+fn arch_family(arch: &str) -> &str {
+    match arch {
+        "x86_64" | "x86_64h" | "amd64" | "x86" | "i386" | "i486" | "i586" | "i686" => "x86",
+        "aarch64" | "aarch64_be" | "arm64" | "arm64e" | "arm64ec" | "arm64_32" => "aarch64",
+        arch if arch.starts_with("arm") || arch.starts_with("thumb") => "arm",
+        arch if arch.starts_with("e2k") => "e2k",
+        arch if arch.starts_with("mips") => "mips",
+        arch if arch.starts_with("powerpc") || arch.starts_with("ppc") => "powerpc",
+        arch if arch.starts_with("riscv") => "riscv",
+        arch if arch.starts_with("s390") => "s390",
+        arch if arch.starts_with("sparc") => "sparc",
+        arch if arch.starts_with("loongarch") => "loongarch",
+        arch if arch.starts_with("wasm") => "wasm",
+        arch => arch,
+    }
+}
+
+fn os_from_component(component: &str) -> Option<&'static str> {
+    Some(match component {
+        c if c.starts_with("linux") => "linux",
+        c if c.starts_with("darwin") || c.starts_with("macos") => "macos",
+        c if c.starts_with("ios") => "ios",
+        "windows" | "mingw32" | "w64" | "cygwin" | "msvc" => "windows",
+        c if c.starts_with("freebsd") => "freebsd",
+        c if c.starts_with("netbsd") => "netbsd",
+        c if c.starts_with("openbsd") => "openbsd",
+        c if c.starts_with("dragonfly") => "dragonfly",
+        c if c.starts_with("solaris") => "solaris",
+        c if c.starts_with("illumos") => "illumos",
+        c if c.starts_with("wasi") => "wasi",
+        "emscripten" => "emscripten",
+        "fuchsia" => "fuchsia",
+        "haiku" => "haiku",
+        "none" | "elf" | "eabi" | "eabihf" => "none",
+        _ => return None,
+    })
+}
+
+fn env_from_component(component: &str) -> Option<&'static str> {
+    Some(match component {
+        c if c.starts_with("gnu") => "gnu",
+        "mingw32" => "gnu",
+        c if c.starts_with("musl") => "musl",
+        "msvc" => "msvc",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TripleMismatch;
+    use crate::target::TargetInfo;
+
+    #[test]
+    fn compiler_triple_mismatch() {
+        let check = |target: &str, triple: &str| {
+            TargetInfo::from_rustc_target(target)
+                .unwrap()
+                .compiler_triple_mismatch(triple)
+        };
+
+        assert_eq!(check("x86_64-unknown-linux-gnu", "x86_64-linux-gnu"), None);
+        assert_eq!(check("i686-unknown-linux-gnu", "x86_64-linux-gnu"), None);
+        assert_eq!(
+            check("aarch64-apple-darwin", "arm64-apple-darwin23.4.0"),
+            None
+        );
+        assert_eq!(check("thumbv7em-none-eabihf", "arm-none-eabi"), None);
+        assert_eq!(check("x86_64-unknown-none", "x86_64-linux-gnu"), None);
+        assert_eq!(check("x86_64-pc-windows-gnu", "x86_64-w64-mingw32"), None);
+        assert_eq!(
+            check("e2k-unknown-linux-gnu", "x86_64-linux-gnu"),
+            Some(TripleMismatch::Arch)
+        );
+        assert_eq!(
+            check("x86_64-pc-windows-gnu", "x86_64-linux-gnu"),
+            Some(TripleMismatch::Os)
+        );
+        assert_eq!(
+            check("x86_64-unknown-linux-musl", "x86_64-linux-gnu"),
+            Some(TripleMismatch::Env)
+        );
+        assert_eq!(
+            check("aarch64-linux-android", "aarch64-unknown-linux-android24"),
+            None
+        );
+    }
+}
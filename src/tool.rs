@@ -1,21 +1,35 @@
 use crate::{
     command_helpers::{run_output, spawn_and_wait_for_output, CargoOutput},
+    lcc,
+    probe_cache::{compiler_identity, ProbeCache},
     run,
     tempfile::NamedTempfile,
-    Error, ErrorKind, OutputKind,
+    CommandKind, Error, ErrorKind, OutputKind,
 };
 use std::{
     borrow::Cow,
     collections::HashMap,
     env,
     ffi::{OsStr, OsString},
+    fmt,
     io::Write,
     path::{Path, PathBuf},
     process::{Command, Output, Stdio},
-    sync::RwLock,
+    sync::{Arc, RwLock},
 };
 
-pub(crate) type CompilerFamilyLookupCache = HashMap<Box<[Box<OsStr>]>, ToolFamily>;
+pub(crate) type CompilerFamilyLookupCache = HashMap<Box<[Box<OsStr>]>, DetectedCompiler>;
+
+/// What was learned about a compiler by preprocessing
+/// `detect_compiler_family.c`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct DetectedCompiler {
+    family: ToolFamily,
+    version: Option<CompilerVersion>,
+    /// The target that the compiler produces objects for when given no
+    /// arguments, e.g. `x86_64-linux-gnu`.
+    target: Option<Arc<str>>,
+}
 
 /// Configuration used to represent an invocation of a C compiler.
 ///
@@ -36,6 +50,51 @@ pub struct Tool {
     pub(crate) cuda: bool,
     pub(crate) removed_args: Vec<OsString>,
     pub(crate) has_internal_target_arg: bool,
+    pub(crate) version: Option<CompilerVersion>,
+    pub(crate) default_target: Option<Arc<str>>,
+    pub(crate) probe_cache: Option<Arc<ToolProbeCache>>,
+    /// The output settings, executor and sink of the `Build` that created
+    /// this tool, for running the compiler from the public methods.
+    pub(crate) cargo_output: Option<CargoOutput>,
+}
+
+/// What the `Build` provides for detecting the family of a compiler.
+#[derive(Clone, Copy)]
+pub(crate) struct DetectionContext<'a> {
+    pub(crate) cached_compiler_family: &'a RwLock<CompilerFamilyLookupCache>,
+    pub(crate) probe_cache: Option<&'a ProbeCache>,
+    pub(crate) cargo_output: &'a CargoOutput,
+    pub(crate) out_dir: Option<&'a Path>,
+    /// The `PATH` that the compiler is looked up in if it is just a name.
+    pub(crate) search_path: Option<&'a OsStr>,
+    /// Set on the commands run to detect the compiler, like `Build::env`
+    /// is on all others.
+    pub(crate) env: &'a [(Arc<OsStr>, Arc<OsStr>)],
+}
+
+/// A command running `path` with `env` set.
+fn command(path: &Path, env: &[(Arc<OsStr>, Arc<OsStr>)]) -> Command {
+    let mut cmd = Command::new(path);
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
+    cmd
+}
+
+type ProbeCacheMap<T> = RwLock<HashMap<Box<[OsString]>, Arc<T>>>;
+
+/// Results of running the compiler from [`Tool`] methods, shared by all tools
+/// created by the same `Build`.
+#[derive(Default)]
+pub(crate) struct ToolProbeCache {
+    predefined_macros: ProbeCacheMap<[(String, String)]>,
+    system_include_dirs: ProbeCacheMap<[PathBuf]>,
+}
+
+impl fmt::Debug for ToolProbeCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolProbeCache").finish_non_exhaustive()
+    }
 }
 
 impl Tool {
@@ -56,37 +115,16 @@ impl Tool {
         cc_tool
     }
 
-    pub(crate) fn new(
-        path: PathBuf,
-        cached_compiler_family: &RwLock<CompilerFamilyLookupCache>,
-        cargo_output: &CargoOutput,
-        out_dir: Option<&Path>,
-    ) -> Self {
-        Self::with_features(
-            path,
-            vec![],
-            false,
-            cached_compiler_family,
-            cargo_output,
-            out_dir,
-        )
+    pub(crate) fn new(path: PathBuf, context: &DetectionContext<'_>) -> Self {
+        Self::with_features(path, vec![], false, context)
     }
 
     pub(crate) fn with_args(
         path: PathBuf,
         args: Vec<String>,
-        cached_compiler_family: &RwLock<CompilerFamilyLookupCache>,
-        cargo_output: &CargoOutput,
-        out_dir: Option<&Path>,
+        context: &DetectionContext<'_>,
     ) -> Self {
-        Self::with_features(
-            path,
-            args,
-            false,
-            cached_compiler_family,
-            cargo_output,
-            out_dir,
-        )
+        Self::with_features(path, args, false, context)
     }
 
     /// Explicitly set the `ToolFamily`, skipping name-based detection.
@@ -101,6 +139,10 @@ impl Tool {
             cuda: false,
             removed_args: Vec::new(),
             has_internal_target_arg: false,
+            version: None,
+            default_target: None,
+            probe_cache: None,
+            cargo_output: None,
         }
     }
 
@@ -108,48 +150,50 @@ impl Tool {
         path: PathBuf,
         args: Vec<String>,
         cuda: bool,
-        cached_compiler_family: &RwLock<CompilerFamilyLookupCache>,
-        cargo_output: &CargoOutput,
-        out_dir: Option<&Path>,
+        context: &DetectionContext<'_>,
     ) -> Self {
-        fn is_zig_cc(path: &Path, cargo_output: &CargoOutput) -> bool {
+        let DetectionContext {
+            cached_compiler_family,
+            probe_cache,
+            cargo_output,
+            out_dir,
+            search_path,
+            env,
+        } = *context;
+
+        fn is_zig_cc(
+            path: &Path,
+            env: &[(Arc<OsStr>, Arc<OsStr>)],
+            cargo_output: &CargoOutput,
+        ) -> bool {
             run_output(
-                Command::new(path).arg("--version"),
+                command(path, env).arg("--version"),
                 // tool detection issues should always be shown as warnings
                 cargo_output,
+                CommandKind::Probe,
             )
             .map(|o| String::from_utf8_lossy(&o).contains("ziglang"))
             .unwrap_or_default()
-                || {
-                    match path.file_name().map(OsStr::to_string_lossy) {
-                        Some(fname) => fname.contains("zig"),
-                        _ => false,
-                    }
-                }
+                || is_named_zig(path)
         }
 
-        fn is_mcst_lcc(path: &Path, cargo_output: &CargoOutput) -> bool {
-            run_output(
-                Command::new(path).arg("--version"),
-                // tool detection issues should always be shown as warnings
-                cargo_output,
-            )
-            .map(|o| String::from_utf8_lossy(&o).starts_with("lcc:"))
-            .unwrap_or_default()
+        fn is_named_zig(path: &Path) -> bool {
+            match path.file_name().map(OsStr::to_string_lossy) {
+                Some(fname) => fname.contains("zig"),
+                _ => false,
+            }
         }
 
-        fn guess_family_from_stdout(
-            stdout: &str,
+        fn accepts_cl_style_flags(
             path: &Path,
             args: &[String],
+            env: &[(Arc<OsStr>, Arc<OsStr>)],
             cargo_output: &CargoOutput,
-        ) -> Result<ToolFamily, Error> {
-            cargo_output.print_debug(&stdout);
-
+        ) -> bool {
             // https://gitlab.kitware.com/cmake/cmake/-/blob/69a2eeb9dff5b60f2f1e5b425002a0fd45b7cadb/Modules/CMakeDetermineCompilerId.cmake#L267-271
             // stdin is set to null to ensure that the help output is never paginated.
-            let accepts_cl_style_flags = run(
-                Command::new(path).args(args).arg("-?").stdin(Stdio::null()),
+            run(
+                command(path, env).args(args).arg("-?").stdin(Stdio::null()),
                 &{
                     // the errors are not errors!
                     let mut cargo_output = cargo_output.clone();
@@ -157,39 +201,86 @@ impl Tool {
                     cargo_output.output = OutputKind::Discard;
                     cargo_output
                 },
+                CommandKind::Probe,
             )
-            .is_ok();
+            .is_ok()
+        }
+
+        /// Identify the compiler from the vendor macros it defines, only
+        /// running it again when they are ambiguous.
+        fn guess_family_from_stdout(
+            stdout: &str,
+            path: &Path,
+            args: &[String],
+            env: &[(Arc<OsStr>, Arc<OsStr>)],
+            cargo_output: &CargoOutput,
+        ) -> Result<ToolFamily, Error> {
+            cargo_output.print_debug(&stdout);
 
             let clang = stdout.contains(r#""clang""#);
-            let mcst_lcc = is_mcst_lcc(path, cargo_output);
-            let gcc = stdout.contains(r#""gcc""#) || mcst_lcc;
+            let gcc = stdout.contains(r#""gcc""#);
             let emscripten = stdout.contains(r#""emscripten""#);
             let vxworks = stdout.contains(r#""VxWorks""#);
+            let mcst_lcc = stdout.contains(r#""lcc""#);
+            let zig = stdout.contains(r#""zig""#);
+            let msvc = stdout
+                .lines()
+                .any(|line| line.trim().starts_with("cc_version_msvc"));
 
-            match (clang, accepts_cl_style_flags, gcc, emscripten, vxworks) {
-                (clang_cl, true, _, false, false) => Ok(ToolFamily::Msvc { clang_cl }),
-                (true, _, _, _, false) | (_, _, _, true, false) => Ok(ToolFamily::Clang {
-                    zig_cc: is_zig_cc(path, cargo_output),
-                }),
-                (false, false, true, _, false) | (_, _, _, _, true) => {
-                    Ok(ToolFamily::Gnu { mcst_lcc })
-                }
-                (false, false, false, false, false) => {
-                    cargo_output.print_warning(&"Compiler family detection failed since it does not define `__clang__`, `__GNUC__`, `__EMSCRIPTEN__` or `__VXWORKS__`, also does not accept cl style flag `-?`, fallback to treating it as GNU");
-                    Err(Error::new(
-                        ErrorKind::ToolFamilyMacroNotFound,
-                        "Expects macro `__clang__`, `__GNUC__` or `__EMSCRIPTEN__`, `__VXWORKS__` or accepts cl style flag `-?`, but found none",
-                    ))
+            // LCC also defines `__GNUC__`.
+            let gnu = if mcst_lcc {
+                ToolFamily::Lcc
+            } else {
+                ToolFamily::Gnu
+            };
+
+            if vxworks {
+                return Ok(gnu);
+            }
+            if msvc && !clang {
+                return Ok(ToolFamily::Msvc { clang_cl: false });
+            }
+            if msvc {
+                // Both clang-cl and Clang targeting MSVC define `_MSC_VER`.
+                let clang_cl = args
+                    .iter()
+                    .rev()
+                    .find_map(|a| a.strip_prefix("--driver-mode="))
+                    .map_or_else(
+                        || accepts_cl_style_flags(path, args, env, cargo_output),
+                        |mode| mode == "cl",
+                    );
+                if clang_cl {
+                    return Ok(ToolFamily::Msvc { clang_cl });
                 }
             }
+            if clang || emscripten {
+                return Ok(ToolFamily::Clang {
+                    zig_cc: zig || is_named_zig(path),
+                });
+            }
+            if gcc || mcst_lcc {
+                return Ok(gnu);
+            }
+
+            // Older versions of MSVC may not print anything recognizable.
+            if accepts_cl_style_flags(path, args, env, cargo_output) {
+                return Ok(ToolFamily::Msvc { clang_cl: false });
+            }
+            cargo_output.print_warning(&"Compiler family detection failed since it does not define `__clang__`, `__GNUC__`, `__EMSCRIPTEN__`, `__VXWORKS__` or `_MSC_VER`, also does not accept cl style flag `-?`, fallback to treating it as GNU");
+            Err(Error::new(
+                ErrorKind::ToolFamilyMacroNotFound,
+                "Expects macro `__clang__`, `__GNUC__`, `__EMSCRIPTEN__`, `__VXWORKS__` or `_MSC_VER` or accepts cl style flag `-?`, but found none",
+            ))
         }
 
         fn detect_family_inner(
             path: &Path,
             args: &[String],
+            env: &[(Arc<OsStr>, Arc<OsStr>)],
             cargo_output: &CargoOutput,
             out_dir: Option<&Path>,
-        ) -> Result<ToolFamily, Error> {
+        ) -> Result<DetectedCompiler, Error> {
             let out_dir = out_dir
                 .map(Cow::Borrowed)
                 .unwrap_or_else(|| Cow::Owned(env::temp_dir()));
@@ -227,7 +318,7 @@ impl Tool {
             let mut compiler_detect_output = cargo_output.clone();
             compiler_detect_output.warnings = compiler_detect_output.debug;
 
-            let mut cmd = Command::new(path);
+            let mut cmd = command(path, env);
             cmd.arg("-E").arg(tmp.path());
 
             // The -Wslash-u-filename warning is normally part of stdout.
@@ -239,15 +330,16 @@ impl Tool {
                 status,
                 stdout,
                 stderr,
-            } = spawn_and_wait_for_output(&mut cmd, &captured_cargo_output)?;
+            } = spawn_and_wait_for_output(&mut cmd, &captured_cargo_output, CommandKind::Probe)?;
 
             let stdout = if [&stdout, &stderr]
                 .iter()
                 .any(|o| String::from_utf8_lossy(o).contains("-Wslash-u-filename"))
             {
                 run_output(
-                    Command::new(path).arg("-E").arg("--").arg(tmp.path()),
+                    command(path, env).arg("-E").arg("--").arg(tmp.path()),
                     &compiler_detect_output,
+                    CommandKind::Probe,
                 )?
             } else {
                 if !status.success() {
@@ -263,32 +355,58 @@ impl Tool {
             };
 
             let stdout = String::from_utf8_lossy(&stdout);
-            guess_family_from_stdout(&stdout, path, args, cargo_output)
+            let family = guess_family_from_stdout(&stdout, path, args, env, cargo_output)?;
+            Ok(DetectedCompiler::from_probe(&stdout, family))
         }
-        let detect_family = |path: &Path, args: &[String]| -> Result<ToolFamily, Error> {
+        let detect_family = |path: &Path, args: &[String]| -> Result<DetectedCompiler, Error> {
             let cache_key = [path.as_os_str()]
                 .iter()
                 .cloned()
                 .chain(args.iter().map(OsStr::new))
                 .map(Into::into)
                 .collect();
-            if let Some(family) = cached_compiler_family.read().unwrap().get(&cache_key) {
-                return Ok(*family);
+            if let Some(detected) = cached_compiler_family.read().unwrap().get(&cache_key) {
+                return Ok(detected.clone());
             }
 
-            let family = detect_family_inner(path, args, cargo_output, out_dir)?;
+            // The compiler may have been detected by another build script.
+            let identity = probe_cache.and_then(|_| compiler_identity(path, search_path));
+            let disk_key = identity.as_ref().map(|identity| {
+                [OsStr::new(identity), path.as_os_str()]
+                    .iter()
+                    .copied()
+                    .chain(args.iter().map(OsStr::new))
+                    .collect::<Vec<_>>()
+            });
+            if let (Some(probe_cache), Some(disk_key)) = (probe_cache, &disk_key) {
+                let detected = probe_cache
+                    .get("family", disk_key)
+                    .and_then(|value| DetectedCompiler::decode(&value));
+                if let Some(detected) = detected {
+                    cached_compiler_family
+                        .write()
+                        .unwrap()
+                        .insert(cache_key, detected.clone());
+                    return Ok(detected);
+                }
+            }
+
+            let detected = detect_family_inner(path, args, env, cargo_output, out_dir)?;
+            if let (Some(probe_cache), Some(disk_key)) = (probe_cache, &disk_key) {
+                probe_cache.insert("family", disk_key, &detected.encode());
+            }
             cached_compiler_family
                 .write()
                 .unwrap()
-                .insert(cache_key, family);
-            Ok(family)
+                .insert(cache_key, detected.clone());
+            Ok(detected)
         };
 
-        let family = detect_family(&path, &args).unwrap_or_else(|e| {
+        let detected = detect_family(&path, &args).unwrap_or_else(|e| {
             cargo_output.print_warning(&format_args!(
                 "Compiler family detection failed due to error: {e}"
             ));
-            match path.file_name().map(OsStr::to_string_lossy) {
+            let family = match path.file_name().map(OsStr::to_string_lossy) {
                 Some(fname) if fname.contains("clang-cl") => ToolFamily::Msvc { clang_cl: true },
                 Some(fname) if fname.ends_with("cl") || fname == "cl.exe" => {
                     ToolFamily::Msvc { clang_cl: false }
@@ -301,12 +419,35 @@ impl Tool {
                         ToolFamily::Msvc { clang_cl: true }
                     } else {
                         ToolFamily::Clang {
-                            zig_cc: is_zig_cc(&path, cargo_output),
+                            zig_cc: is_zig_cc(&path, env, cargo_output),
                         }
                     }
                 }
                 Some(fname) if fname.contains("zig") => ToolFamily::Clang { zig_cc: true },
-                _ => ToolFamily::gnu(),
+                _ => {
+                    // LCC prints e.g. `lcc:1.25.17:Jun-19-2021:e2k-v4-linux`.
+                    let version = run_output(
+                        command(&path, env).arg("--version"),
+                        cargo_output,
+                        CommandKind::Probe,
+                    )
+                    .ok()
+                    .and_then(|o| lcc::parse_version(&String::from_utf8_lossy(&o)));
+                    return DetectedCompiler {
+                        family: if version.is_some() {
+                            ToolFamily::Lcc
+                        } else {
+                            ToolFamily::Gnu
+                        },
+                        version,
+                        target: None,
+                    };
+                }
+            };
+            DetectedCompiler {
+                family,
+                version: None,
+                target: None,
             }
         });
 
@@ -316,10 +457,14 @@ impl Tool {
             cc_wrapper_args: Vec::new(),
             args: Vec::new(),
             env: Vec::new(),
-            family,
+            family: detected.family,
             cuda,
             removed_args: Vec::new(),
             has_internal_target_arg: false,
+            version: detected.version,
+            default_target: detected.target,
+            probe_cache: None,
+            cargo_output: None,
         }
     }
 
@@ -396,7 +541,10 @@ impl Tool {
         };
         cmd.args(&self.cc_wrapper_args);
 
-        cmd.args(self.args.iter().filter(|a| !self.removed_args.contains(a)));
+        cmd.args(self.args.iter().filter(|a| {
+            !self.removed_args.contains(a)
+                && (self.family != ToolFamily::Lcc || !lcc::is_unsupported_flag(a))
+        }));
 
         for (k, v) in self.env.iter() {
             cmd.env(k, v);
@@ -463,7 +611,7 @@ impl Tool {
 
     /// Whether the tool is GNU Compiler Collection-like.
     pub fn is_like_gnu(&self) -> bool {
-        matches!(self.family, ToolFamily::Gnu { .. })
+        matches!(self.family, ToolFamily::Gnu | ToolFamily::Lcc)
     }
 
     /// Whether the tool is Clang-like.
@@ -489,7 +637,7 @@ impl Tool {
 
     /// Whether the tool is MCST-LCC-like.
     pub fn is_like_mcst_lcc(&self) -> bool {
-        matches!(self.family, ToolFamily::Gnu { mcst_lcc: true })
+        matches!(self.family, ToolFamily::Lcc)
     }
 
     /// Whether the tool is `clang-cl`-based MSVC-like.
@@ -497,6 +645,235 @@ impl Tool {
         matches!(self.family, ToolFamily::Msvc { clang_cl: true })
     }
 
+    /// The family of this compiler, as used by [`Tool::version`].
+    pub fn family(&self) -> CompilerFamily {
+        match self.family {
+            ToolFamily::Gnu => CompilerFamily::Gnu,
+            ToolFamily::Lcc => CompilerFamily::McstLcc,
+            ToolFamily::Clang { .. } => CompilerFamily::Clang,
+            ToolFamily::Msvc { clang_cl: false } => CompilerFamily::Msvc,
+            ToolFamily::Msvc { clang_cl: true } => CompilerFamily::ClangCl,
+        }
+    }
+
+    /// The version of this compiler.
+    ///
+    /// The version is read from the family-specific predefined macros
+    /// (`__GNUC__`, `__clang_major__`, `__LCC__` or `_MSC_VER`), so e.g. for
+    /// `clang-cl` this is the version of Clang, not that of the MSVC it
+    /// emulates.
+    ///
+    /// The macros are normally collected by the same preprocessor run that
+    /// detects the compiler family, which is cached. If that didn't happen
+    /// (e.g. for MSVC found through the registry), the preprocessor is run
+    /// again on each call.
+    pub fn version(&self) -> Result<CompilerVersion, Error> {
+        if let Some(version) = self.version {
+            return Ok(version);
+        }
+
+        let tmp = probe_file(
+            "detect_compiler_family.c",
+            include_bytes!("detect_compiler_family.c"),
+        )?;
+        let mut cmd = self.to_command();
+        cmd.arg("-E").arg(tmp.path());
+        let stdout = run_output(&mut cmd, &self.probe_cargo_output(), CommandKind::Probe)?;
+
+        CompilerVersion::from_probe(&String::from_utf8_lossy(&stdout), self.family).ok_or_else(
+            || {
+                Error::new(
+                    ErrorKind::ToolFamilyMacroNotFound,
+                    format!(
+                        "could not determine the version of `{}`",
+                        self.path.display()
+                    ),
+                )
+            },
+        )
+    }
+
+    /// The macros predefined by this compiler for its current arguments, as
+    /// `(name, value)` pairs.
+    ///
+    /// For function-like macros, the name includes the parameter list, e.g.
+    /// `("__INT64_C(c)", "c ## L")`.
+    ///
+    /// This runs the preprocessor with `-dM -E` for GCC, Clang and LCC, and
+    /// with `-EP -Zc:preprocessor -PD` for MSVC (which needs Visual Studio
+    /// 2022 or newer). If this tool was created by a `Build`, the result is
+    /// cached and shared with other tools from the same `Build`.
+    pub fn predefined_macros(&self) -> Result<Vec<(String, String)>, Error> {
+        let macros = self.cached(
+            |cache| &cache.predefined_macros,
+            || {
+                let tmp = probe_file("predefined_macros.c", b"")?;
+                let mut cmd = self.to_command();
+                match self.family {
+                    ToolFamily::Msvc { clang_cl: false } => {
+                        cmd.args(["-nologo", "-EP", "-Zc:preprocessor", "-PD"]);
+                    }
+                    ToolFamily::Msvc { clang_cl: true } => {
+                        cmd.args(["-E", "/clang:-dM"]);
+                    }
+                    ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => {
+                        cmd.args(["-dM", "-E"]);
+                    }
+                }
+                cmd.arg(tmp.path());
+                let stdout = run_output(&mut cmd, &self.probe_cargo_output(), CommandKind::Probe)?;
+                Ok(parse_macro_definitions(&String::from_utf8_lossy(&stdout)).into())
+            },
+        )?;
+        Ok(macros.to_vec())
+    }
+
+    /// The directories searched by this compiler for `#include <...>`, in
+    /// order.
+    ///
+    /// For GCC, Clang and LCC, these are parsed from the output of `-E -v`,
+    /// so they also include any directories added with `-I` or `-isystem`
+    /// in the tool's arguments. For MSVC, they are read from the `INCLUDE`
+    /// environment variable, which is set up when the compiler is found
+    /// through the Visual Studio installation.
+    ///
+    /// If this tool was created by a `Build`, the result is cached and shared
+    /// with other tools from the same `Build`.
+    pub fn system_include_dirs(&self) -> Result<Vec<PathBuf>, Error> {
+        let dirs = self.cached(
+            |cache| &cache.system_include_dirs,
+            || {
+                if self.family == (ToolFamily::Msvc { clang_cl: false }) {
+                    #[allow(clippy::disallowed_methods)]
+                    let include = self
+                        .env
+                        .iter()
+                        .find(|(k, _)| k == "INCLUDE")
+                        .map(|(_, v)| v.clone())
+                        .or_else(|| env::var_os("INCLUDE"))
+                        .unwrap_or_default();
+                    return Ok(env::split_paths(&include)
+                        .filter(|dir| !dir.as_os_str().is_empty())
+                        .collect());
+                }
+
+                let tmp = probe_file("system_include_dirs.c", b"")?;
+                let mut cmd = self.to_command();
+                cmd.arg("-E").arg("-v").arg(tmp.path());
+                cmd.stdin(Stdio::null());
+                // The search list is printed to stderr, so it has to be piped.
+                let mut cargo_output = self.probe_cargo_output();
+                cargo_output.warnings = true;
+                let Output { status, stderr, .. } =
+                    spawn_and_wait_for_output(&mut cmd, &cargo_output, CommandKind::Probe)?;
+                if !status.success() {
+                    return Err(Error::new(
+                        ErrorKind::ToolExecError,
+                        format!(
+                            "command did not execute successfully (status code {status}): {cmd:?}"
+                        ),
+                    ));
+                }
+                Ok(parse_include_search_list(&String::from_utf8_lossy(&stderr)).into())
+            },
+        )?;
+        Ok(dirs.to_vec())
+    }
+
+    /// Look up the result of running the compiler with its current command
+    /// line in the probe cache, or compute and store it.
+    fn cached<T: ?Sized>(
+        &self,
+        map: impl FnOnce(&ToolProbeCache) -> &ProbeCacheMap<T>,
+        compute: impl FnOnce() -> Result<Arc<T>, Error>,
+    ) -> Result<Arc<T>, Error> {
+        let map = match &self.probe_cache {
+            Some(cache) => map(cache),
+            None => return compute(),
+        };
+
+        let cmd = self.to_command();
+        let key: Box<[OsString]> = std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(OsStr::to_os_string)
+            .chain(cmd.get_envs().map(|(k, v)| {
+                let mut kv = k.to_os_string();
+                kv.push("=");
+                kv.push(v.unwrap_or_default());
+                kv
+            }))
+            .collect();
+        if let Some(value) = map.read().unwrap().get(&key) {
+            return Ok(value.clone());
+        }
+
+        let value = compute()?;
+        map.write().unwrap().insert(key, value.clone());
+        Ok(value)
+    }
+
+    /// The target triple that this compiler (with its current arguments)
+    /// produces code for, as reported by the compiler itself.
+    ///
+    /// This uses `-dumpmachine` for GCC and LCC, `-print-target-triple` for
+    /// Clang, and the `Target:` line of `--version` for `clang-cl`. The triple
+    /// is returned as-is, so it is e.g. `x86_64-linux-gnu` for GCC on Debian,
+    /// not the `rustc` target name.
+    ///
+    /// MSVC's `cl.exe` can't report its target, so an error is returned.
+    pub fn target_triple(&self) -> Result<String, Error> {
+        let mut cmd = self.to_command();
+        let parse_version = match self.family {
+            ToolFamily::Gnu | ToolFamily::Lcc => {
+                cmd.arg("-dumpmachine");
+                false
+            }
+            ToolFamily::Clang { .. } => {
+                cmd.arg("-print-target-triple");
+                false
+            }
+            ToolFamily::Msvc { clang_cl: true } => {
+                cmd.arg("--version");
+                true
+            }
+            ToolFamily::Msvc { clang_cl: false } => {
+                return Err(Error::new(
+                    ErrorKind::ToolExecError,
+                    "MSVC does not support querying its target triple",
+                ));
+            }
+        };
+
+        let stdout = run_output(&mut cmd, &self.probe_cargo_output(), CommandKind::Probe)?;
+        let stdout = String::from_utf8_lossy(&stdout);
+        let triple = if parse_version {
+            stdout
+                .lines()
+                .find_map(|line| line.strip_prefix("Target:"))
+                .unwrap_or_default()
+        } else {
+            stdout.lines().next().unwrap_or_default()
+        }
+        .trim();
+
+        if triple.is_empty() {
+            return Err(Error::new(
+                ErrorKind::ToolExecError,
+                format!("`{}` did not report its target triple", self.path.display()),
+            ));
+        }
+        Ok(triple.to_string())
+    }
+
+    /// Output settings for running the compiler from the public methods,
+    /// which shouldn't print any cargo instructions.
+    fn probe_cargo_output(&self) -> CargoOutput {
+        let mut cargo_output = self.cargo_output.clone().unwrap_or_else(CargoOutput::new);
+        cargo_output.metadata = false;
+        cargo_output.warnings = cargo_output.debug;
+        cargo_output
+    }
+
     /// Supports using `--` delimiter to separate arguments and path to source files.
     pub(crate) fn supports_path_delimiter(&self) -> bool {
         // homebrew clang and zig-cc does not support this while stock version does
@@ -504,6 +881,254 @@ impl Tool {
     }
 }
 
+impl DetectedCompiler {
+    /// Parse the output of preprocessing `detect_compiler_family.c` with a
+    /// compiler of `family`.
+    fn from_probe(stdout: &str, family: ToolFamily) -> Self {
+        let value = |marker: &str| {
+            stdout.lines().find_map(|line| {
+                let value = line.trim().strip_prefix(marker)?.trim();
+                value.strip_prefix('"')?.strip_suffix('"')
+            })
+        };
+        // Without the architecture and OS, nothing useful can be said.
+        let target = match (value("cc_target_arch"), value("cc_target_os")) {
+            (Some(arch), Some(os)) => Some(
+                match value("cc_target_env") {
+                    Some(env) => format!("{arch}-{os}-{env}"),
+                    None => format!("{arch}-{os}"),
+                }
+                .into(),
+            ),
+            _ => None,
+        };
+        Self {
+            family,
+            version: CompilerVersion::from_probe(stdout, family),
+            target,
+        }
+    }
+
+    /// Serialize for the on-disk probe cache.
+    fn encode(&self) -> String {
+        let mut encoded = match self.family {
+            ToolFamily::Gnu => "gnu",
+            ToolFamily::Lcc => "lcc",
+            ToolFamily::Clang { zig_cc: false } => "clang",
+            ToolFamily::Clang { zig_cc: true } => "zig",
+            ToolFamily::Msvc { clang_cl: false } => "msvc",
+            ToolFamily::Msvc { clang_cl: true } => "clang-cl",
+        }
+        .to_string();
+        if let Some(version) = self.version {
+            encoded.push_str(&format!(" version={version}"));
+        }
+        if let Some(target) = &self.target {
+            encoded.push_str(&format!(" target={target}"));
+        }
+        encoded
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        let mut parts = s.split(' ');
+        let family = match parts.next()? {
+            "gnu" => ToolFamily::Gnu,
+            "lcc" => ToolFamily::Lcc,
+            "clang" => ToolFamily::Clang { zig_cc: false },
+            "zig" => ToolFamily::Clang { zig_cc: true },
+            "msvc" => ToolFamily::Msvc { clang_cl: false },
+            "clang-cl" => ToolFamily::Msvc { clang_cl: true },
+            _ => return None,
+        };
+        let mut detected = Self {
+            family,
+            version: None,
+            target: None,
+        };
+        for part in parts {
+            match part.split_once('=')? {
+                ("version", version) => {
+                    let mut numbers = version.split('.').map(str::parse);
+                    let mut next = || numbers.next().and_then(Result::ok);
+                    detected.version = Some(CompilerVersion::new(next()?, next()?, next()?));
+                }
+                ("target", target) => detected.target = Some(target.into()),
+                _ => return None,
+            }
+        }
+        Some(detected)
+    }
+}
+
+/// Write `contents` to a temporary file that the compiler can be run on.
+fn probe_file(name: &str, contents: &[u8]) -> Result<NamedTempfile, Error> {
+    let mut tmp = NamedTempfile::new(&env::temp_dir(), name).map_err(|err| {
+        Error::new(
+            ErrorKind::IOError,
+            format!("failed to create {name} temp file: {err}"),
+        )
+    })?;
+    let mut tmp_file = tmp.take_file().unwrap();
+    tmp_file.write_all(contents)?;
+    // Close the file handle now, so that the compiler can open it on Windows.
+    tmp_file.flush()?;
+    drop(tmp_file);
+    Ok(tmp)
+}
+
+/// Parse the `#define` lines printed by `-dM -E` (or MSVC's `-PD`).
+fn parse_macro_definitions(stdout: &str) -> Vec<(String, String)> {
+    stdout
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#define "))
+        .map(|definition| {
+            // Function-like macros have no space between the name and the
+            // parameter list.
+            let name_end = match (definition.find('('), definition.find(' ')) {
+                (Some(paren), space) if space.map_or(true, |space| paren < space) => definition
+                    .find(')')
+                    .map_or(definition.len(), |close| close + 1),
+                (_, Some(space)) => space,
+                (_, None) => definition.len(),
+            };
+            let (name, value) = definition.split_at(name_end);
+            (name.to_string(), value.trim().to_string())
+        })
+        .collect()
+}
+
+/// Parse the `#include <...>` search list printed to stderr by `-E -v`.
+fn parse_include_search_list(stderr: &str) -> Vec<PathBuf> {
+    stderr
+        .lines()
+        .skip_while(|line| !line.starts_with("#include <...> search starts here:"))
+        .skip(1)
+        .take_while(|line| !line.starts_with("End of search list."))
+        .map(|line| {
+            let line = line.trim();
+            PathBuf::from(line.strip_suffix(" (framework directory)").unwrap_or(line))
+        })
+        .collect()
+}
+
+/// The family of a compiler, see [`Tool::family`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CompilerFamily {
+    /// GCC, or another compiler that identifies as it.
+    Gnu,
+    /// Clang, including AppleClang and `zig cc`.
+    Clang,
+    /// MSVC's `cl.exe`.
+    Msvc,
+    /// Clang's MSVC-compatible driver, `clang-cl`.
+    ClangCl,
+    /// The MCST LCC compiler for Elbrus.
+    McstLcc,
+}
+
+impl fmt::Display for CompilerFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CompilerFamily::Gnu => "GCC",
+            CompilerFamily::Clang => "Clang",
+            CompilerFamily::Msvc => "MSVC",
+            CompilerFamily::ClangCl => "clang-cl",
+            CompilerFamily::McstLcc => "LCC",
+        })
+    }
+}
+
+/// The version of a compiler, see [`Tool::version`].
+///
+/// For MSVC, this is the version of `cl.exe` (e.g. `19.29.30153`), not
+/// the version of Visual Studio. For LCC, `1.25.17` is read from
+/// `__LCC__ == 125` and `__LCC_MINOR__ == 17`.
+///
+/// Versions can also be created from `(major, minor)` and
+/// `(major, minor, patch)` tuples.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompilerVersion {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl CompilerVersion {
+    /// Create a new version.
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// The major version.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// The minor version.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// The patch version.
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+
+    /// Parse the version of `family` from the output of preprocessing
+    /// `detect_compiler_family.c`.
+    pub(crate) fn from_probe(stdout: &str, family: ToolFamily) -> Option<Self> {
+        let marker = match family {
+            ToolFamily::Gnu => "cc_version_gnu",
+            ToolFamily::Lcc => "cc_version_lcc",
+            ToolFamily::Clang { .. } | ToolFamily::Msvc { clang_cl: true } => "cc_version_clang",
+            ToolFamily::Msvc { clang_cl: false } => "cc_version_msvc",
+        };
+        let mut numbers = stdout
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(marker))?
+            .split_whitespace()
+            .map(|n| n.parse::<u32>().ok());
+        let mut next = || numbers.next().flatten();
+        let first = next()?;
+
+        Some(match family {
+            // `__LCC__` is e.g. `125` for 1.25, `__LCC_MINOR__` is the patch.
+            ToolFamily::Lcc => Self::new(first / 100, first % 100, next().unwrap_or(0)),
+            // `_MSC_VER` is e.g. `1929` for 19.29, `_MSC_FULL_VER` is
+            // `192930153`, with the build number as the last five digits.
+            ToolFamily::Msvc { clang_cl: false } => Self::new(
+                first / 100,
+                first % 100,
+                next().map_or(0, |full| full % 100_000),
+            ),
+            _ => Self::new(first, next().unwrap_or(0), next().unwrap_or(0)),
+        })
+    }
+}
+
+impl From<(u32, u32)> for CompilerVersion {
+    fn from((major, minor): (u32, u32)) -> Self {
+        Self::new(major, minor, 0)
+    }
+}
+
+impl From<(u32, u32, u32)> for CompilerVersion {
+    fn from((major, minor, patch): (u32, u32, u32)) -> Self {
+        Self::new(major, minor, patch)
+    }
+}
+
+impl fmt::Display for CompilerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 /// Represents the family of tools this tool belongs to.
 ///
 /// Each family of tools differs in how and what arguments they accept.
@@ -512,7 +1137,10 @@ impl Tool {
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ToolFamily {
     /// Tool is GNU Compiler Collection-like.
-    Gnu { mcst_lcc: bool },
+    Gnu,
+    /// Tool is the MCST LCC compiler for Elbrus. It accepts most of GCC's
+    /// flags, see the `lcc` module for where it differs.
+    Lcc,
     /// Tool is Clang-like. It differs from the GCC in a sense that it accepts superset of flags
     /// and its cross-compilation approach is different.
     Clang { zig_cc: bool },
@@ -521,12 +1149,8 @@ pub enum ToolFamily {
 }
 
 impl ToolFamily {
-    pub(crate) fn gnu() -> ToolFamily {
-        ToolFamily::Gnu { mcst_lcc: false }
-    }
-
     pub(crate) fn is_gnu(&self) -> bool {
-        matches!(self, ToolFamily::Gnu { .. })
+        matches!(self, ToolFamily::Gnu | ToolFamily::Lcc)
     }
 
     /// What the flag to request debug info for this family of tools look like
@@ -540,7 +1164,14 @@ impl ToolFamily {
             ToolFamily::Msvc { .. } => {
                 cmd.push_cc_arg("-Z7".into());
             }
-            ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => {
+            ToolFamily::Lcc => {
+                // LCC has no reduced levels of debug info.
+                cmd.push_cc_arg("-g".into());
+                if let Some(v) = dwarf_version {
+                    cmd.push_cc_arg(format!("-gdwarf-{v}").into());
+                }
+            }
+            ToolFamily::Gnu | ToolFamily::Clang { .. } => {
                 match debug_opt {
                     // From https://doc.rust-lang.org/cargo/reference/profiles.html#debug
                     "" | "0" | "false" | "none" => {
@@ -577,7 +1208,7 @@ impl ToolFamily {
     /// What the flag to force frame pointers.
     pub(crate) fn add_force_frame_pointer(&self, cmd: &mut Tool) {
         match *self {
-            ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => {
+            ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => {
                 cmd.push_cc_arg("-fno-omit-frame-pointer".into());
             }
             _ => (),
@@ -588,14 +1219,14 @@ impl ToolFamily {
     pub(crate) fn warnings_flags(&self) -> &'static str {
         match *self {
             ToolFamily::Msvc { .. } => "-W4",
-            ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => "-Wall",
+            ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => "-Wall",
         }
     }
 
     pub(crate) fn warnings_suppression_flags(&self) -> &'static str {
         match *self {
             ToolFamily::Msvc { .. } => "-W0",
-            ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => "-w",
+            ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => "-w",
         }
     }
 
@@ -603,7 +1234,7 @@ impl ToolFamily {
     pub(crate) fn extra_warnings_flags(&self) -> Option<&'static str> {
         match *self {
             ToolFamily::Msvc { .. } => None,
-            ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => Some("-Wextra"),
+            ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => Some("-Wextra"),
         }
     }
 
@@ -611,7 +1242,7 @@ impl ToolFamily {
     pub(crate) fn warnings_to_errors_flag(&self) -> &'static str {
         match *self {
             ToolFamily::Msvc { .. } => "-WX",
-            ToolFamily::Gnu { .. } | ToolFamily::Clang { .. } => "-Werror",
+            ToolFamily::Gnu | ToolFamily::Lcc | ToolFamily::Clang { .. } => "-Werror",
         }
     }
 
@@ -619,3 +1250,165 @@ impl ToolFamily {
         matches!(*self, ToolFamily::Clang { .. })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_include_search_list, parse_macro_definitions, CompilerVersion, DetectedCompiler,
+        ToolFamily,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn detected_compiler_from_probe() {
+        let stdout = "\
+# 1 \"detect_compiler_family.c\"
+#pragma message \"gcc\"
+cc_version_gnu 12 2 0
+cc_target_arch \"x86_64\"
+cc_target_os \"linux\"
+cc_target_env \"gnu\"
+";
+        let detected = DetectedCompiler::from_probe(stdout, ToolFamily::Gnu);
+        assert_eq!(detected.version, Some((12, 2, 0).into()));
+        assert_eq!(detected.target.as_deref(), Some("x86_64-linux-gnu"));
+
+        let stdout = "cc_target_arch \"aarch64\"\ncc_target_os \"android\"\n";
+        let detected = DetectedCompiler::from_probe(stdout, ToolFamily::Gnu);
+        assert_eq!(detected.target.as_deref(), Some("aarch64-android"));
+
+        // The OS is required.
+        let stdout = "cc_target_arch \"arm\"\n";
+        let detected = DetectedCompiler::from_probe(stdout, ToolFamily::Gnu);
+        assert_eq!(detected.target, None);
+    }
+
+    #[test]
+    fn detected_compiler_roundtrip() {
+        for (family, version, target) in [
+            (
+                ToolFamily::Gnu,
+                Some(CompilerVersion::new(12, 2, 0)),
+                Some("x86_64-linux-gnu"),
+            ),
+            (ToolFamily::Lcc, None, None),
+            (
+                ToolFamily::Clang { zig_cc: true },
+                Some((17, 0, 6).into()),
+                None,
+            ),
+            (
+                ToolFamily::Msvc { clang_cl: false },
+                None,
+                Some("x86_64-windows-msvc"),
+            ),
+            (
+                ToolFamily::Msvc { clang_cl: true },
+                Some((18, 1, 8).into()),
+                None,
+            ),
+        ] {
+            let detected = DetectedCompiler {
+                family,
+                version,
+                target: target.map(Into::into),
+            };
+            let encoded = detected.encode();
+            assert_eq!(
+                DetectedCompiler::decode(&encoded),
+                Some(detected),
+                "{encoded}"
+            );
+        }
+        assert_eq!(DetectedCompiler::decode("unknown"), None);
+        assert_eq!(DetectedCompiler::decode("gnu 12.2.0"), None);
+    }
+
+    #[test]
+    fn macro_definitions() {
+        let stdout = "\
+#define __STDC__ 1
+#define __INT64_C(c) c ## L
+#define __VERSION__ \"12.2.0 (with spaces)\"
+#define _MSC_VER 1939
+#define EMPTY
+# 1 \"<built-in>\"
+";
+        let pairs = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            parse_macro_definitions(stdout),
+            pairs(&[
+                ("__STDC__", "1"),
+                ("__INT64_C(c)", "c ## L"),
+                ("__VERSION__", "\"12.2.0 (with spaces)\""),
+                ("_MSC_VER", "1939"),
+                ("EMPTY", ""),
+            ])
+        );
+    }
+
+    #[test]
+    fn include_search_list() {
+        let stderr = "\
+ignoring nonexistent directory \"/usr/local/include/x86_64-linux-gnu\"
+#include \"...\" search starts here:
+ /quoted
+#include <...> search starts here:
+ /usr/lib/gcc/x86_64-linux-gnu/12/include
+ /usr/include
+ /System/Library/Frameworks (framework directory)
+End of search list.
+";
+        assert_eq!(
+            parse_include_search_list(stderr),
+            [
+                "/usr/lib/gcc/x86_64-linux-gnu/12/include",
+                "/usr/include",
+                "/System/Library/Frameworks",
+            ]
+            .iter()
+            .map(PathBuf::from)
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn version_from_probe() {
+        let stdout = "\
+# 1 \"detect_compiler_family.c\"
+#pragma message \"gcc\"
+cc_version_gnu 4 2 1
+cc_version_clang 17 0 6
+cc_version_lcc 125 17
+cc_version_msvc 1929 192930153
+";
+        let version = |family| CompilerVersion::from_probe(stdout, family);
+
+        assert_eq!(version(ToolFamily::Gnu), Some((4, 2, 1).into()));
+        assert_eq!(
+            version(ToolFamily::Clang { zig_cc: false }),
+            Some((17, 0, 6).into())
+        );
+        assert_eq!(
+            version(ToolFamily::Msvc { clang_cl: true }),
+            Some((17, 0, 6).into())
+        );
+        assert_eq!(version(ToolFamily::Lcc), Some((1, 25, 17).into()));
+        assert_eq!(
+            version(ToolFamily::Msvc { clang_cl: false }),
+            Some((19, 29, 30153).into())
+        );
+
+        // Undefined macros are left as-is by the preprocessor.
+        assert_eq!(
+            CompilerVersion::from_probe("cc_version_lcc 123 __LCC_MINOR__", ToolFamily::Lcc),
+            Some((1, 23, 0).into())
+        );
+        assert_eq!(CompilerVersion::from_probe("", ToolFamily::Gnu), None);
+    }
+}
@@ -158,3 +158,20 @@ pub(crate) fn cargo_env_var(key: &str) -> Result<String, Error> {
         ))
     }
 }
+
+/// Write `contents` to `path`, leaving the file untouched (and its mtime
+/// stable) if it already has exactly that content.
+///
+/// Returns whether the file was written.
+pub(crate) fn write_if_changed(path: &Path, contents: &[u8]) -> Result<bool, Error> {
+    if let Ok(existing) = std::fs::read(path) {
+        if existing == contents {
+            return Ok(false);
+        }
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)?;
+    Ok(true)
+}
@@ -0,0 +1,153 @@
+//! Discovery of [wasi-sdk], and of the WASI sysroot and its libraries for
+//! each WASI target.
+//!
+//! [wasi-sdk]: https://github.com/WebAssembly/wasi-sdk
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::apple_sdk::compare_versions;
+
+/// A wasi-sdk installation, e.g. `/opt/wasi-sdk`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WasiSdk {
+    pub(crate) path: PathBuf,
+}
+
+impl WasiSdk {
+    /// Read the wasi-sdk at `path`, if it is one, i.e. it has a sysroot.
+    pub(crate) fn read(path: &Path) -> Option<WasiSdk> {
+        let sdk = WasiSdk {
+            path: path.to_path_buf(),
+        };
+        sdk.sysroot().is_dir().then_some(sdk)
+    }
+
+    /// Find the first wasi-sdk in `paths`.
+    pub(crate) fn find(paths: impl IntoIterator<Item = PathBuf>) -> Option<WasiSdk> {
+        paths.into_iter().find_map(|path| WasiSdk::read(&path))
+    }
+
+    pub(crate) fn sysroot(&self) -> PathBuf {
+        self.path.join("share").join("wasi-sysroot")
+    }
+
+    /// The directory with `clang`, the per-target `<target>-clang` and
+    /// `llvm-ar`.
+    pub(crate) fn bin_dir(&self) -> PathBuf {
+        self.path.join("bin")
+    }
+}
+
+/// Where wasi-sdk is usually installed, as the newest first: the release
+/// archives unpack to e.g. `wasi-sdk-25.0-x86_64-linux`, which is often
+/// renamed to `wasi-sdk`.
+pub(crate) fn sdk_locations() -> Vec<PathBuf> {
+    if !cfg!(unix) {
+        return Vec::new();
+    }
+    let mut locations = vec![PathBuf::from("/opt/wasi-sdk")];
+    let mut versioned: Vec<_> = fs::read_dir("/opt")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            name.starts_with("wasi-sdk-").then(|| path.clone())
+        })
+        .collect();
+    versioned.sort_by(|a, b| compare_versions(&sdk_version(b), &sdk_version(a)));
+    locations.extend(versioned);
+    locations.push(PathBuf::from("/usr/local/wasi-sdk"));
+    locations
+}
+
+fn sdk_version(path: &Path) -> String {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.trim_start_matches("wasi-sdk-")
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Where sysroots installed without wasi-sdk usually are, e.g. by the
+/// `wasi-libc` packages of Linux distributions and Homebrew.
+pub(crate) fn sysroot_locations() -> Vec<PathBuf> {
+    if !cfg!(unix) {
+        return Vec::new();
+    }
+    [
+        "/usr/share/wasi-sysroot",
+        "/usr/local/share/wasi-sysroot",
+        "/opt/homebrew/share/wasi-sysroot",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect()
+}
+
+/// The directory of the target's libraries in `sysroot`, e.g.
+/// `lib/wasm32-wasip1-threads`. Sysroots from before wasi-sdk 22 only have
+/// `lib/wasm32-wasi` and `lib/wasm32-wasi-threads` for preview 1.
+pub(crate) fn lib_dir(sysroot: &Path, rustc_target: &str) -> PathBuf {
+    let lib = sysroot.join("lib");
+    let legacy = match rustc_target {
+        "wasm32-wasip1" => Some("wasm32-wasi"),
+        "wasm32-wasip1-threads" => Some("wasm32-wasi-threads"),
+        _ => None,
+    };
+    match legacy {
+        Some(legacy) if !lib.join(rustc_target).is_dir() && lib.join(legacy).is_dir() => {
+            lib.join(legacy)
+        }
+        _ => lib.join(rustc_target),
+    }
+}
+
+/// The target to pass to Clang, if it is not rustc's LLVM target: for
+/// `wasm32-wasip1-threads` that is `wasm32-wasi`, with which Clang looks for
+/// the libraries in `lib/wasm32-wasi-threads`, which newer sysroots only
+/// have as `lib/wasm32-wasip1-threads`.
+pub(crate) fn clang_target(sysroot: &Path, rustc_target: &str) -> Option<&'static str> {
+    match rustc_target {
+        "wasm32-wasip1-threads" if sysroot.join("lib").join(rustc_target).is_dir() => {
+            Some("wasm32-wasip1-threads")
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{lib_dir, WasiSdk};
+
+    #[test]
+    fn find() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("wasi-sdk-20.0");
+        let new = dir.path().join("wasi-sdk-25.0-x86_64-linux");
+        fs::create_dir_all(old.join("share/wasi-sysroot/lib/wasm32-wasi")).unwrap();
+        fs::create_dir_all(new.join("share/wasi-sysroot/lib/wasm32-wasip2")).unwrap();
+
+        let sdk = WasiSdk::find([dir.path().join("missing"), new.clone(), old.clone()]).unwrap();
+        assert_eq!(sdk.path, new);
+        assert_eq!(sdk.bin_dir(), new.join("bin"));
+        assert_eq!(
+            lib_dir(&sdk.sysroot(), "wasm32-wasip2"),
+            new.join("share/wasi-sysroot/lib/wasm32-wasip2")
+        );
+
+        let sysroot = WasiSdk::read(&old).unwrap().sysroot();
+        assert_eq!(
+            lib_dir(&sysroot, "wasm32-wasip1"),
+            sysroot.join("lib/wasm32-wasi")
+        );
+        assert_eq!(
+            lib_dir(&sysroot, "wasm32-wasip1-threads"),
+            sysroot.join("lib/wasm32-wasip1-threads")
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Support for using `zig cc` as the C and C++ compiler, which cross-compiles
+//! to many targets without a separate toolchain for each.
+
+use std::path::Path;
+
+use crate::{Error, ErrorKind, TargetInfo};
+
+/// Whether `path` is Zig itself, which has to be called as `zig cc`,
+/// `zig c++` or `zig ar`, rather than one of the wrapper scripts around it.
+pub(crate) fn is_zig(path: &Path) -> bool {
+    path.file_stem().map_or(false, |stem| stem == "zig")
+}
+
+/// The Zig target for a Rust target, as passed with `-target`, e.g.
+/// `x86_64-linux-gnu.2.17` or `aarch64-macos.11.0`.
+///
+/// `glibc_version` is appended to GNU targets on Linux, and
+/// `apple_deployment_target` to Apple ones.
+pub(crate) fn target(
+    target: &TargetInfo<'_>,
+    glibc_version: Option<&str>,
+    apple_deployment_target: Option<&str>,
+) -> Result<String, Error> {
+    let big_endian = target.full_arch.contains("eb");
+    let arch = match target.arch {
+        "x86" => "x86",
+        "x86_64" => "x86_64",
+        "aarch64" if target.full_arch.contains("be") => "aarch64_be",
+        "aarch64" | "arm64ec" => "aarch64",
+        "arm" if target.full_arch.starts_with("thumb") => {
+            if big_endian {
+                "thumbeb"
+            } else {
+                "thumb"
+            }
+        }
+        "arm" if big_endian => "armeb",
+        "arm" => "arm",
+        "riscv32" => "riscv32",
+        "riscv64" => "riscv64",
+        "loongarch64" => "loongarch64",
+        "mips" if target.full_arch.ends_with("el") => "mipsel",
+        "mips" => "mips",
+        "mips64" if target.full_arch.ends_with("el") => "mips64el",
+        "mips64" => "mips64",
+        "powerpc" => "powerpc",
+        "powerpc64" if target.full_arch.ends_with("le") => "powerpc64le",
+        "powerpc64" => "powerpc64",
+        "s390x" => "s390x",
+        "sparc64" => "sparc64",
+        "wasm32" => "wasm32",
+        "wasm64" => "wasm64",
+        "e2k" => return Err(Error::new(
+            ErrorKind::InvalidTarget,
+            "Zig does not support Elbrus 2000 (e2k) targets, as there is no LLVM backend for them",
+        )),
+        arch => {
+            return Err(Error::new(
+                ErrorKind::InvalidTarget,
+                format!("Zig does not support the target architecture {arch}"),
+            ))
+        }
+    };
+
+    let os = match target.os {
+        "macos" | "ios" | "tvos" | "watchos" | "visionos" => target.os,
+        "none" => "freestanding",
+        "android" => "linux",
+        "linux" | "windows" | "freebsd" | "netbsd" | "openbsd" | "dragonfly" | "haiku"
+        | "illumos" | "solaris" | "wasi" | "emscripten" | "uefi" | "fuchsia" => target.os,
+        os => {
+            return Err(Error::new(
+                ErrorKind::InvalidTarget,
+                format!("Zig does not support the target OS {os}"),
+            ))
+        }
+    };
+
+    let abi = match (target.os, target.env, target.abi) {
+        ("android", _, "eabi") => "androideabi".to_string(),
+        ("android", ..) => "android".to_string(),
+        (_, "sim", _) => "simulator".to_string(),
+        (_, "macabi", _) => "macabi".to_string(),
+        // E.g. `gnueabihf` and `gnuabi64`.
+        (_, env @ ("gnu" | "musl"), abi @ ("eabi" | "eabihf" | "abi64")) => format!("{env}{abi}"),
+        (_, env @ ("gnu" | "musl" | "msvc"), _) => env.to_string(),
+        (_, "", abi @ ("eabi" | "eabihf")) => abi.to_string(),
+        _ => String::new(),
+    };
+    let version = if target.vendor == "apple" {
+        apple_deployment_target
+    } else if abi.starts_with("gnu") && os == "linux" {
+        glibc_version
+    } else {
+        None
+    };
+    Ok(versioned(arch, os, &abi, version))
+}
+
+fn versioned(arch: &str, os: &str, abi: &str, version: Option<&str>) -> String {
+    // The OS version goes after the OS, the glibc version after the ABI.
+    match (abi, version) {
+        ("", Some(version)) => format!("{arch}-{os}.{version}"),
+        ("", None) => format!("{arch}-{os}"),
+        (abi, Some(version)) if abi.starts_with("gnu") => format!("{arch}-{os}-{abi}.{version}"),
+        (abi, Some(version)) => format!("{arch}-{os}.{version}-{abi}"),
+        (abi, None) => format!("{arch}-{os}-{abi}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TargetInfo;
+
+    fn target(rustc_target: &str, glibc: Option<&str>, apple: Option<&str>) -> String {
+        let info = TargetInfo::from_rustc_target(rustc_target).unwrap();
+        super::target(&info, glibc, apple).unwrap()
+    }
+
+    #[test]
+    fn targets() {
+        assert_eq!(
+            target("x86_64-unknown-linux-gnu", Some("2.17"), None),
+            "x86_64-linux-gnu.2.17"
+        );
+        assert_eq!(
+            target("armv7-unknown-linux-gnueabihf", Some("2.28"), None),
+            "arm-linux-gnueabihf.2.28"
+        );
+        assert_eq!(
+            target("i686-unknown-linux-musl", Some("2.17"), None),
+            "x86-linux-musl"
+        );
+        assert_eq!(
+            target("aarch64-apple-darwin", None, Some("11.0")),
+            "aarch64-macos.11.0"
+        );
+        assert_eq!(
+            target("aarch64-apple-ios-sim", None, Some("14.0")),
+            "aarch64-ios.14.0-simulator"
+        );
+        assert_eq!(
+            target("x86_64-pc-windows-gnu", None, None),
+            "x86_64-windows-gnu"
+        );
+        assert_eq!(
+            target("thumbv7em-none-eabihf", None, None),
+            "thumb-freestanding-eabihf"
+        );
+        assert_eq!(target("wasm32-wasip1", None, None), "wasm32-wasi");
+        assert_eq!(
+            target("armv7-linux-androideabi", None, None),
+            "arm-linux-androideabi"
+        );
+        assert_eq!(
+            target("mips64el-unknown-linux-gnuabi64", Some("2.17"), None),
+            "mips64el-linux-gnuabi64.2.17"
+        );
+
+        let e2k = TargetInfo::from_rustc_target("e2k-unknown-linux-gnu").unwrap();
+        assert!(super::target(&e2k, None, None).is_err());
+    }
+}
@@ -20,6 +20,9 @@ fn ar_env() {
 fn various() {
     let mut env = support::GlobalEnv::lock();
     env.remove("AR");
+    env.remove("ANDROID_NDK_HOME");
+    env.remove("ANDROID_NDK_ROOT");
+    env.remove("ANDROID_HOME");
 
     let ar = get_ar_for_target("x86_64-unknown-linux-gnu");
     assert_eq!(ar, "ar");
@@ -30,6 +33,9 @@ fn various() {
     let ar = get_ar_for_target("riscv64gc-unknown-openbsd");
     assert_eq!(ar, "ar");
 
+    let ar = get_ar_for_target("e2kv4-unknown-linux-gnu");
+    assert_eq!(ar, "e2k-linux-ar");
+
     let ar = get_ar_for_target("i686-wrs-vxworks");
     assert_eq!(ar, "wr-ar");
 
@@ -21,6 +21,7 @@ fn gnu_no_warnings_if_cflags() {
 /// 3. Builder flags.
 /// 4. Environment flags.
 #[test]
+#[allow(deprecated)]
 fn cflags_order() {
     let mut test = Test::gnu();
 
@@ -48,6 +48,18 @@ impl Test {
         env.remove("CXX");
         env.remove("AR");
 
+        // An NDK or wasi-sdk found through these would be used instead of the shims.
+        for var in [
+            "ANDROID_NDK_HOME",
+            "ANDROID_NDK_ROOT",
+            "ANDROID_HOME",
+            "ANDROID_PLATFORM",
+            "WASI_SDK_PATH",
+            "WASI_SYSROOT",
+        ] {
+            env.remove(var);
+        }
+
         // Some tests check that a flag is *not* present.  These tests might fail if the flag is set in the
         // CFLAGS or CXXFLAGS environment variables.  This clears the CFLAGS and CXXFLAGS
         // variables to make sure that the tests can run correctly.
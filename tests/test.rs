@@ -1,5 +1,8 @@
 #![allow(clippy::disallowed_methods)]
 
+use std::fs;
+use std::sync::{Arc, Mutex};
+
 use crate::support::Test;
 
 mod support;
@@ -70,6 +73,7 @@ fn gnu_debug() {
     let test = Test::gnu();
     test.gcc()
         .target("e2k-unknown-linux-gnu")
+        .host("e2k-unknown-linux-gnu")
         .debug(true)
         .file("foo.c")
         .compile("foo");
@@ -267,6 +271,35 @@ fn gnu_i686() {
     }
 }
 
+#[test]
+fn gnu_target_defaults() {
+    let targets: &[(&str, &[&str])] = &[
+        (
+            "armv7-unknown-linux-gnueabihf",
+            &[
+                "-march=armv7-a",
+                "-marm",
+                "-mfpu=vfpv3-d16",
+                "-mfloat-abi=hard",
+            ],
+        ),
+        ("i686-unknown-linux-gnu", &["-march=pentium4"]),
+    ];
+    for (target, flags) in targets {
+        let test = Test::gnu();
+        test.gcc()
+            .target(target)
+            .host(target)
+            .file("foo.c")
+            .compile("foo");
+
+        let cmd = test.cmd(0);
+        for flag in *flags {
+            cmd.must_have(flag);
+        }
+    }
+}
+
 #[test]
 fn gnu_i686_pic() {
     for vendor in &["unknown-linux-gnu", "apple-darwin"] {
@@ -311,6 +344,231 @@ fn gnu_aarch64_none_no_pic() {
     }
 }
 
+#[test]
+fn gnu_e2k_cross_sysroot() {
+    let mut test = Test::gnu();
+    let root = test.td.path().join("lcc");
+    fs::create_dir_all(root.join("bin")).unwrap();
+    fs::create_dir_all(root.join("e2k-linux").join("sysroot")).unwrap();
+    let exe = format!("e2k-linux-gcc{}", std::env::consts::EXE_SUFFIX);
+    fs::copy(test.td.path().join("cc"), root.join("bin").join(exe)).unwrap();
+    let mut path = vec![root.join("bin")];
+    path.extend(std::env::split_paths(&std::env::var_os("PATH").unwrap()));
+    test.env.set("PATH", std::env::join_paths(path).unwrap());
+
+    let compiler = test
+        .gcc()
+        .target("e2kv4-unknown-linux-gnu")
+        .host("x86_64-unknown-linux-gnu")
+        .get_compiler();
+    assert_eq!(compiler.path(), std::path::Path::new("e2k-linux-gcc"));
+    let sysroot = root
+        .canonicalize()
+        .unwrap()
+        .join("e2k-linux")
+        .join("sysroot");
+    let flag = format!("--sysroot={}", sysroot.display());
+    assert!(
+        compiler.args().iter().any(|arg| *arg == *flag),
+        "{:?}",
+        compiler.args()
+    );
+}
+
+/// What LCC 1.26.12 prints for `detect_compiler_family.c`.
+const LCC_DETECT_FAMILY: &str = r#"#pragma message "gcc"
+cc_version_gnu 9 3 0
+cc_version_lcc 126 12
+#pragma message "lcc"
+cc_target_arch "e2k"
+cc_target_os "linux"
+cc_target_env "gnu""#;
+
+#[test]
+fn lcc_flags() {
+    let mut test = Test::gnu();
+    test.shim("lcc");
+    test.env.set("CC_SHIM_DETECT_FAMILY", LCC_DETECT_FAMILY);
+    let lcc = test.td.path().join("lcc");
+
+    // Detected from `__LCC__`, without running `lcc --version`.
+    let compiler = test
+        .gcc()
+        .compiler(&lcc)
+        .target("e2kv4-unknown-linux-gnu")
+        .host("e2kv4-unknown-linux-gnu")
+        .get_compiler();
+    assert!(compiler.is_like_mcst_lcc());
+    assert!(compiler.is_like_gnu());
+    assert_eq!(compiler.version().unwrap(), (1, 26, 12).into());
+
+    test.gcc()
+        .compiler(&lcc)
+        .target("e2kv4-unknown-linux-gnu")
+        .host("e2kv4-unknown-linux-gnu")
+        .debug_str("line-tables-only")
+        .flag("-fcf-protection=full")
+        .flag("-fno-plt")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0)
+        .must_have("-march=elbrus-v4")
+        .must_have("-g")
+        .must_not_have("-g1")
+        .must_have("-gdwarf-2")
+        .must_not_have("-fcf-protection=full")
+        .must_not_have("-fno-plt");
+    drop(test);
+
+    // Processor targets only tune for the processor.
+    let mut test = Test::gnu();
+    test.shim("lcc");
+    test.env.set("CC_SHIM_DETECT_FAMILY", LCC_DETECT_FAMILY);
+    test.gcc()
+        .compiler(test.td.path().join("lcc"))
+        .target("e2k8c-unknown-linux-gnu")
+        .host("e2k8c-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0)
+        .must_have("-mtune=elbrus-8c")
+        .must_not_have("-march=elbrus-v4");
+    drop(test);
+
+    // If the detection fails, LCC is still recognized by `--version`.
+    let mut test = Test::gnu();
+    test.shim("lcc");
+    test.env
+        .set("CC_SHIM_VERSION", "lcc:1.25.17:Jun-19-2021:e2k-v4-linux");
+    let compiler = test
+        .gcc()
+        .compiler(test.td.path().join("lcc"))
+        .target("e2kv4-unknown-linux-gnu")
+        .host("e2kv4-unknown-linux-gnu")
+        .get_compiler();
+    assert!(compiler.is_like_mcst_lcc());
+    assert_eq!(compiler.version().unwrap(), (1, 25, 17).into());
+}
+
+#[test]
+fn detect_family_with_build_env() {
+    let test = Test::gnu();
+    test.shim("lcc");
+
+    // The compiler is only found in the `PATH` set on the `Build`, and is
+    // detected in its environment.
+    let compiler = test
+        .gcc()
+        .compiler("lcc")
+        .env("CC_SHIM_DETECT_FAMILY", LCC_DETECT_FAMILY)
+        .target("e2kv4-unknown-linux-gnu")
+        .host("e2kv4-unknown-linux-gnu")
+        .get_compiler();
+    assert!(compiler.is_like_mcst_lcc());
+    assert_eq!(compiler.version().unwrap(), (1, 26, 12).into());
+}
+
+#[test]
+fn get_target_info() {
+    let mut test = Test::gnu();
+    let target = test
+        .gcc()
+        .target("armv7-unknown-linux-gnueabihf")
+        .host("x86_64-unknown-linux-gnu")
+        .get_target_info();
+    assert_eq!(target.full_arch(), "armv7");
+    assert_eq!(target.arch(), "arm");
+    assert_eq!(target.vendor(), "unknown");
+    assert_eq!(target.os(), "linux");
+    assert_eq!(target.env(), "gnu");
+    assert_eq!(target.abi(), "eabihf");
+    assert_eq!(target.llvm_target(), "armv7-unknown-linux-gnueabihf");
+    assert_eq!(target.apple_sdk_name(), None);
+    assert_eq!(target.apple_deployment_target(), None);
+    assert_eq!(target.pointer_width(), Some(32));
+    assert_eq!(target.max_atomic_width(), Some(64));
+    assert!(target.is_cross_compile());
+
+    // Avoid dependency on the SDK in test.
+    test.env.set("XROS_DEPLOYMENT_TARGET", "1.0");
+    let target = test
+        .gcc()
+        .target("aarch64-apple-visionos-sim")
+        .host("aarch64-apple-visionos-sim")
+        .get_target_info();
+    assert_eq!(target.env(), "sim");
+    assert_eq!(target.llvm_target(), "arm64-apple-xros1.0-simulator");
+    assert_eq!(target.apple_sdk_name(), Some("xrsimulator"));
+    assert_eq!(target.apple_deployment_target(), Some("1.0"));
+    assert!(!target.is_cross_compile());
+}
+
+#[test]
+fn register_target() {
+    let test = Test::clang();
+    let mut spec = cc::TargetSpec::new("aarch64-unknown-none", "aarch64");
+    spec.os("acmeos").cpu("cortex-a53");
+    test.gcc()
+        .compiler(test.td.path().join("clang"))
+        .register_target("aarch64-acme-acmeos", spec)
+        .target("aarch64-acme-acmeos")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0)
+        .must_have("--target=aarch64-unknown-none")
+        .must_have("-mcpu=cortex-a53");
+}
+
+#[test]
+fn target_spec_json() {
+    let mut test = Test::gnu();
+    let specs = test.td.path().join("specs");
+    fs::create_dir(&specs).unwrap();
+    let spec = specs.join("x86_64-acme-none.json");
+    fs::write(
+        &spec,
+        r#"{
+            "llvm-target": "x86_64-unknown-none-elf",
+            "arch": "x86_64",
+            "os": "acme",
+            "cpu": "x86-64-v2",
+            "features": "-avx,+soft-float",
+            "target-pointer-width": "64"
+        }"#,
+    )
+    .unwrap();
+    test.env.set("RUST_TARGET_PATH", &specs);
+
+    test.gcc()
+        .target("x86_64-acme-none")
+        .host("x86_64-acme-none")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0)
+        .must_have("-march=x86-64-v2")
+        .must_have("-mno-avx")
+        .must_have("-msoft-float");
+
+    let target = test.gcc().target("x86_64-acme-none").get_target_info();
+    assert_eq!(target.os(), "acme");
+    assert_eq!(target.vendor(), "unknown");
+    assert_eq!(target.llvm_target(), "x86_64-unknown-none-elf");
+
+    // A path to a spec works without `RUST_TARGET_PATH`.
+    test.env.remove("RUST_TARGET_PATH");
+    let target = test.gcc().target(spec.to_str().unwrap()).get_target_info();
+    assert_eq!(target.full_arch(), "x86_64");
+    assert_eq!(target.os(), "acme");
+
+    let err = test
+        .gcc()
+        .target(specs.join("missing.json").to_str().unwrap())
+        .try_get_target_info()
+        .unwrap_err();
+    assert!(err.to_string().contains("missing.json"), "{}", err);
+}
+
 #[test]
 fn gnu_uefi_no_pic() {
     for arch in &["aarch64", "i686", "x86_64"] {
@@ -345,6 +603,59 @@ fn gnu_include() {
     test.cmd(0).must_have("-I").must_have("foo/bar");
 }
 
+#[test]
+fn gnu_clang_args() {
+    let test = Test::gnu();
+    let args = test
+        .gcc()
+        .target("x86_64-unknown-linux-gnu")
+        .include("foo/bar")
+        .define("FOO", "1")
+        .std("c11")
+        .clang_args();
+
+    assert_eq!(
+        args,
+        [
+            "--target=x86_64-unknown-linux-gnu",
+            "-m64",
+            "-std=c11",
+            "-I",
+            "foo/bar",
+            "-DFOO=1",
+        ]
+    );
+}
+
+#[test]
+fn gnu_configure_file() {
+    let test = Test::gnu();
+    let input = test.td.path().join("config.h.in");
+    fs::write(
+        &input,
+        "#define VERSION \"@VERSION@\"\n#cmakedefine HAVE_FOO\n#cmakedefine01 HAVE_BAR\n",
+    )
+    .unwrap();
+
+    test.gcc()
+        .configure_file(
+            &input,
+            "gen/config.h",
+            [("VERSION", "1.0"), ("HAVE_BAR", "ON")],
+        )
+        .file("foo.c")
+        .compile("foo");
+
+    let gen_dir = test.td.path().join("gen");
+    assert_eq!(
+        fs::read_to_string(gen_dir.join("config.h")).unwrap(),
+        "#define VERSION \"1.0\"\n/* #undef HAVE_FOO */\n#define HAVE_BAR 1\n",
+    );
+    test.cmd(0)
+        .must_have("-I")
+        .must_have(gen_dir.to_str().unwrap());
+}
+
 #[test]
 fn gnu_define() {
     let test = Test::gnu();
@@ -365,6 +676,7 @@ fn gnu_compile_assembly() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn gnu_shared() {
     let test = Test::gnu();
     test.gcc()
@@ -397,6 +709,27 @@ fn gnu_flag_if_supported() {
         .must_not_have("-std=c++11");
 }
 
+#[test]
+fn gnu_flags_if_supported() {
+    if cfg!(windows) {
+        return;
+    }
+    let test = Test::gnu();
+    test.gcc()
+        .file("foo.c")
+        .flags_if_supported(["-Wall", "-Wflag-does-not-exist", "-Wextra"])
+        .first_supported_flag(["-Wother-flag-does-not-exist", "-Wshadow", "-Wundef"])
+        .first_supported_flag(["-Wflag-does-not-exist"])
+        .compile("foo");
+
+    test.cmd(0)
+        .must_have_in_order("-Wall", "-Wextra")
+        .must_have_in_order("-Wextra", "-Wshadow")
+        .must_not_have("-Wflag-does-not-exist")
+        .must_not_have("-Wother-flag-does-not-exist")
+        .must_not_have("-Wundef");
+}
+
 #[cfg(not(windows))]
 #[test]
 fn gnu_flag_if_supported_cpp() {
@@ -411,6 +744,7 @@ fn gnu_flag_if_supported_cpp() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn gnu_static() {
     let test = Test::gnu();
     test.gcc()
@@ -476,6 +810,33 @@ fn msvc_include() {
     test.cmd(0).must_have("-I").must_have("foo/bar");
 }
 
+#[test]
+fn msvc_clang_args() {
+    let test = Test::msvc();
+    let args = test
+        .gcc()
+        .include("foo/bar")
+        .define("FOO", None)
+        .std("c++17")
+        .cpp(true)
+        .clang_args();
+
+    assert_eq!(
+        args,
+        [
+            "--target=x86_64-pc-windows-msvc",
+            "-D_MT",
+            "-D_DLL",
+            "-std=c++17",
+            "-I",
+            "foo/bar",
+            "-DFOO",
+            "-x",
+            "c++",
+        ]
+    );
+}
+
 #[test]
 fn msvc_define() {
     let test = Test::msvc();
@@ -641,6 +1002,58 @@ fn gnu_apple_deployment_target() {
     }
 }
 
+#[test]
+fn gnu_apple_sdks_dir() {
+    let mut test = Test::gnu();
+    let sdks = test.td.path().join("SDK");
+    for (name, version) in [("iPhoneOS16.4", "16.4"), ("iPhoneOS17.2", "17.2")] {
+        let sdk = sdks.join(format!("{name}.sdk"));
+        fs::create_dir_all(&sdk).unwrap();
+        fs::write(
+            sdk.join("SDKSettings.json"),
+            format!(
+                r#"{{
+                    "CanonicalName": "iphoneos{version}",
+                    "Version": "{version}",
+                    "SupportedTargets": {{
+                        "iphoneos": {{
+                            "DefaultDeploymentTarget": "{version}",
+                            "MinimumDeploymentTarget": "12.0"
+                        }}
+                    }}
+                }}"#
+            ),
+        )
+        .unwrap();
+    }
+    test.env.set("APPLE_SDKS_DIR", test.td.path());
+    test.env.remove("SDKROOT");
+    test.env.remove("IPHONEOS_DEPLOYMENT_TARGET");
+
+    test.shim("fake-gcc")
+        .gcc()
+        .compiler("fake-gcc")
+        .target("aarch64-apple-ios")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+
+    test.cmd(0)
+        .must_have_in_order("-isysroot", sdks.join("iPhoneOS17.2.sdk").to_str().unwrap())
+        .must_have("-miphoneos-version-min=17.2");
+
+    // An explicit deployment target is used as-is.
+    test.env.set("IPHONEOS_DEPLOYMENT_TARGET", "10.0");
+    test.gcc()
+        .compiler("fake-gcc")
+        .target("aarch64-apple-ios")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+
+    test.cmd(3).must_have("-miphoneos-version-min=10.0");
+}
+
 #[cfg(target_os = "macos")]
 #[test]
 fn macos_cpp_minimums() {
@@ -876,6 +1289,188 @@ fn clang_android() {
     }
 }
 
+#[test]
+fn clang_android_ndk() {
+    let mut test = Test::new();
+    let ndk = test.td.path().join("android-sdk/ndk/26.1.10909125");
+    let bin = ndk.join("toolchains/llvm/prebuilt/linux-x86_64/bin");
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(
+        ndk.join("source.properties"),
+        "Pkg.Revision = 26.1.10909125\n",
+    )
+    .unwrap();
+    let bin_in_td = bin.strip_prefix(test.td.path()).unwrap();
+    test.shim(bin_in_td.join("clang").to_str().unwrap())
+        .shim(bin_in_td.join("llvm-ar").to_str().unwrap());
+    test.env
+        .set("ANDROID_HOME", test.td.path().join("android-sdk"));
+
+    for (i, &(target, api_level, clang_target)) in [
+        ("aarch64-linux-android", None, "aarch64-linux-android21"),
+        (
+            "armv7-linux-androideabi",
+            None,
+            "armv7a-linux-androideabi21",
+        ),
+        ("x86_64-linux-android", Some(28), "x86_64-linux-android28"),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let mut build = test.gcc();
+        if let Some(api_level) = api_level {
+            build.android_api_level(api_level);
+        }
+        build
+            .target(target)
+            .host("x86_64-unknown-linux-gnu")
+            .file("foo.c")
+            .compile("foo");
+
+        // The compile, then `llvm-ar cq` and `llvm-ar s`.
+        test.cmd(3 * i as u32)
+            .must_have(format!("--target={clang_target}"))
+            .must_have("-DANDROID");
+        test.cmd(3 * i as u32 + 1).must_have("cq");
+    }
+
+    test.env.set("ANDROID_PLATFORM", "android-24");
+    test.gcc()
+        .target("i686-linux-android")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(9)
+        .must_have("--target=i686-linux-android24")
+        .must_have("-mstackrealign");
+}
+
+#[test]
+fn clang_wasi_sdk() {
+    let mut test = Test::new();
+    let sdk = test.td.path().join("wasi-sdk");
+    let sysroot = sdk.join("share/wasi-sysroot");
+    fs::create_dir_all(sdk.join("bin")).unwrap();
+    fs::create_dir_all(sysroot.join("lib/wasm32-wasip2")).unwrap();
+    fs::create_dir_all(sysroot.join("lib/wasm32-wasip1-threads")).unwrap();
+    test.shim("wasi-sdk/bin/wasm32-wasip2-clang")
+        .shim("wasi-sdk/bin/clang")
+        .shim("wasi-sdk/bin/llvm-ar");
+    test.env.set("WASI_SDK_PATH", &sdk);
+
+    test.gcc()
+        .target("wasm32-wasip2")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0)
+        .must_have("--target=wasm32-wasip2")
+        .must_have(format!("--sysroot={}", sysroot.display()))
+        .must_not_have("-pthread");
+    // `llvm-ar` from wasi-sdk.
+    test.cmd(1).must_have("cq");
+
+    // Without a per-target `clang`, the plain one is used.
+    test.gcc()
+        .target("wasm32-wasip1-threads")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(3)
+        .must_have("--target=wasm32-wasip1-threads")
+        .must_have(format!("--sysroot={}", sysroot.display()))
+        .must_have("-pthread");
+}
+
+#[test]
+fn clang_cross_sysroot() {
+    let mut test = Test::new();
+    let cross = test.td.path().join("cross");
+    fs::create_dir_all(cross.join("bin")).unwrap();
+    fs::create_dir_all(cross.join("aarch64-linux-gnu/include")).unwrap();
+    fs::create_dir_all(cross.join("lib/gcc-cross/aarch64-linux-gnu/12")).unwrap();
+    test.shim("clang")
+        .shim("ar")
+        .shim("cross/bin/aarch64-linux-gnu-gcc");
+    test.env.set("PATH", cross.join("bin"));
+    test.env.remove("CROSS_COMPILE");
+    test.env.remove("RUSTC_LINKER");
+
+    test.gcc()
+        .compiler(test.td.path().join("clang"))
+        .archiver(test.td.path().join("ar"))
+        .target("aarch64-unknown-linux-gnu")
+        .host("x86_64-unknown-linux-gnu")
+        .detect_cross_sysroot(true)
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0)
+        .must_have(format!(
+            "--sysroot={}",
+            cross.join("aarch64-linux-gnu").display()
+        ))
+        .must_have(format!("--gcc-toolchain={}", cross.display()));
+
+    // An explicit sysroot replaces the detected one.
+    let sysroot = test.td.path().join("sysroot");
+    test.gcc()
+        .compiler(test.td.path().join("clang"))
+        .archiver(test.td.path().join("ar"))
+        .target("aarch64-unknown-linux-gnu")
+        .host("x86_64-unknown-linux-gnu")
+        .detect_cross_sysroot(true)
+        .sysroot(&sysroot)
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(3)
+        .must_have(format!("--sysroot={}", sysroot.display()))
+        .must_not_have(format!("--gcc-toolchain={}", cross.display()));
+}
+
+#[test]
+fn zig_cc() {
+    let mut test = Test::new();
+    test.shim("zig");
+
+    test.gcc()
+        .use_zig(true)
+        .glibc_version("2.17")
+        .target("aarch64-unknown-linux-gnu")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0)
+        .must_have_in_order("cc", "-target")
+        .must_have_in_order("-target", "aarch64-linux-gnu.2.17")
+        .must_not_have("--target=aarch64-unknown-linux-gnu");
+    test.cmd(1).must_have_in_order("ar", "cq");
+
+    // `CXX=zig` is called as `zig c++`.
+    test.env.set("CXX", test.td.path().join("zig"));
+    test.env.set("MACOSX_DEPLOYMENT_TARGET", "11.0");
+    test.gcc()
+        .cpp(true)
+        .target("x86_64-apple-darwin")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(3)
+        .must_have_in_order("c++", "-target")
+        .must_have("x86_64-macos.11.0")
+        .must_not_have("-arch");
+
+    let err = test
+        .gcc()
+        .cpp(true)
+        .target("e2k-unknown-linux-gnu")
+        .host("x86_64-unknown-linux-gnu")
+        .file("foo.c")
+        .try_compile("foo")
+        .unwrap_err();
+    assert!(err.to_string().contains("e2k"), "{}", err);
+}
+
 #[test]
 fn parent_dir_file_path() {
     // Regression test for issue #172
@@ -1058,3 +1653,369 @@ mod msvc_clang_cl_tests {
         );
     }
 }
+
+#[test]
+fn gnu_compiler_target_mismatch() {
+    let test = Test::gnu();
+    let err = test
+        .gcc()
+        .target("riscv64gc-unknown-openbsd")
+        .env("CC_SHIM_TARGET_TRIPLE", "x86_64-linux-gnu")
+        .file("foo.c")
+        .try_compile("foo")
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("produces x86_64-linux-gnu objects but target is riscv64gc-unknown-openbsd"),
+        "{}",
+        err
+    );
+
+    drop(test);
+
+    // Only the OS differs, which is reported as a warning.
+    let test = Test::gnu();
+    test.gcc()
+        .target("x86_64-unknown-freebsd")
+        .env("CC_SHIM_TARGET_TRIPLE", "x86_64-linux-gnu")
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0).must_have("foo.c");
+}
+
+#[test]
+fn gnu_musl_gcc_target() {
+    let test = Test::gnu();
+    test.shim("musl-gcc");
+    let mismatch = |compiler: &str| {
+        let sink = Arc::new(RecordingSink::default());
+        test.gcc()
+            .compiler(test.td.path().join(compiler))
+            .target("x86_64-unknown-linux-musl")
+            .env("CC_SHIM_TARGET_TRIPLE", "x86_64-linux-gnu")
+            .output_sink(sink.clone())
+            .file("foo.c")
+            .compile("foo");
+        let warnings = sink.warnings.lock().unwrap();
+        warnings
+            .iter()
+            .any(|warning| warning.contains("produces x86_64-linux-gnu objects"))
+    };
+
+    // `musl-gcc` reports the target of the GCC it wraps.
+    assert!(!mismatch("musl-gcc"));
+    assert!(mismatch("cc"));
+}
+
+#[test]
+fn gnu_glibc_version_without_zig() {
+    let test = Test::gnu();
+    let sink = Arc::new(RecordingSink::default());
+    test.gcc()
+        .glibc_version("2.17")
+        .output_sink(sink.clone())
+        .file("foo.c")
+        .compile("foo");
+    test.cmd(0).must_not_have("2.17");
+    let warnings = sink.warnings.lock().unwrap();
+    assert!(
+        warnings
+            .iter()
+            .filter(|warning| warning.starts_with("glibc_version is specified"))
+            .count()
+            == 1,
+        "{:?}",
+        warnings
+    );
+}
+
+#[test]
+fn gnu_probe_cache() {
+    let mut test = Test::gnu();
+    let cache_dir = test.td.path().join("probe-cache");
+    test.env
+        .set("CC_PROBE_CACHE_DIR", cache_dir.to_str().unwrap());
+
+    assert!(test.gcc().is_flag_supported("-Wall").unwrap());
+    let cache_file = cache_dir.join("probes.txt");
+    let contents = fs::read_to_string(&cache_file).unwrap();
+    assert!(contents.lines().any(|line| line.starts_with("family:")));
+    assert!(contents.lines().any(|line| line.ends_with("\t1")));
+
+    // A new `Build` answers from the cache instead of running the compiler.
+    fs::write(&cache_file, contents.replace("\t1", "\t0")).unwrap();
+    assert!(!test.gcc().is_flag_supported("-Wall").unwrap());
+}
+
+#[test]
+fn gnu_keep_going() {
+    let test = Test::gnu();
+    let err = test
+        .gcc()
+        .env("CC_SHIM_FAIL_ON", "bar.c")
+        .keep_going(true)
+        .file("foo.c")
+        .file("bar.c")
+        .file("baz.c")
+        .try_compile("foo")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("1 of 3 source files failed to compile"),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("bar.c:1:1: error: simulated failure"),
+        "{}",
+        err
+    );
+    assert!(!err.contains("foo.c"), "{}", err);
+    // All sources were compiled.
+    test.cmd(0).must_have("foo.c");
+    test.cmd(1).must_have("bar.c");
+    test.cmd(2).must_have("baz.c");
+
+    drop(test);
+
+    // Without `keep_going`, only a single failure is reported.
+    let test = Test::gnu();
+    let err = test
+        .gcc()
+        .env("CC_SHIM_FAIL_ON", "foo.c")
+        .file("foo.c")
+        .file("bar.c")
+        .try_compile("foo")
+        .unwrap_err()
+        .to_string();
+    assert!(!err.contains("failed to compile"), "{}", err);
+}
+
+#[derive(Default)]
+struct RecordingSink {
+    metadata: Mutex<Vec<String>>,
+    warnings: Mutex<Vec<String>>,
+    stderr: Mutex<Vec<String>>,
+}
+
+impl cc::OutputSink for RecordingSink {
+    fn metadata(&self, line: &str) {
+        self.metadata.lock().unwrap().push(line.to_string());
+    }
+    fn warning(&self, message: &str) {
+        self.warnings.lock().unwrap().push(message.to_string());
+    }
+    fn debug(&self, _message: &str) {}
+    fn compiler_stdout(&self, _output: &[u8]) {}
+    fn compiler_stderr(&self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line).into_owned();
+        self.stderr.lock().unwrap().push(line);
+    }
+}
+
+#[test]
+fn gnu_output_sink() {
+    let test = Test::gnu();
+    let sink = Arc::new(RecordingSink::default());
+    test.gcc()
+        .output_sink(sink.clone())
+        .file("foo.c")
+        .compile("foo");
+    let metadata = sink.metadata.lock().unwrap();
+    assert!(
+        metadata.contains(&"cargo:rustc-link-lib=static=foo".to_string()),
+        "{:?}",
+        metadata
+    );
+    assert!(
+        metadata
+            .iter()
+            .any(|line| line.starts_with("cargo:rustc-link-search=native=")),
+        "{:?}",
+        metadata
+    );
+
+    drop(test);
+
+    let test = Test::gnu();
+    let sink = Arc::new(RecordingSink::default());
+    test.gcc()
+        .output_sink(sink.clone())
+        .env("CC_SHIM_FAIL_ON", "foo.c")
+        .file("foo.c")
+        .try_compile("foo")
+        .unwrap_err();
+    assert_eq!(
+        *sink.stderr.lock().unwrap(),
+        ["foo.c:1:1: error: simulated failure"]
+    );
+}
+
+#[derive(Default)]
+struct RecordingExecutor {
+    kinds: Mutex<Vec<cc::CommandKind>>,
+    args: Mutex<Vec<Vec<String>>>,
+}
+
+impl cc::CommandExecutor for RecordingExecutor {
+    fn spawn(
+        &self,
+        cmd: &mut std::process::Command,
+        kind: cc::CommandKind,
+    ) -> std::io::Result<std::process::Child> {
+        self.kinds.lock().unwrap().push(kind);
+        self.args.lock().unwrap().push(
+            cmd.get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+        );
+        if kind == cc::CommandKind::Compile {
+            cmd.arg("-DEXECUTOR");
+        }
+        cmd.spawn()
+    }
+}
+
+#[test]
+fn gnu_executor() {
+    let test = Test::gnu();
+    let executor = Arc::new(RecordingExecutor::default());
+    test.gcc()
+        .executor(executor.clone())
+        .file("foo.c")
+        .compile("foo");
+
+    test.cmd(0).must_have("-DEXECUTOR");
+    let kinds = executor.kinds.lock().unwrap();
+    assert!(kinds.contains(&cc::CommandKind::Compile), "{:?}", kinds);
+    assert!(kinds.contains(&cc::CommandKind::Archive), "{:?}", kinds);
+    drop(kinds);
+    drop(test);
+
+    let test = Test::gnu();
+    let executor = Arc::new(RecordingExecutor::default());
+    test.gcc()
+        .executor(executor.clone())
+        .flag_if_supported("-Wall")
+        .file("foo.c")
+        .compile("foo");
+    let kinds = executor.kinds.lock().unwrap();
+    assert!(kinds.contains(&cc::CommandKind::Probe), "{:?}", kinds);
+}
+
+#[test]
+fn gnu_executor_probes() {
+    let test = Test::gnu();
+    let executor = Arc::new(RecordingExecutor::default());
+    let mut build = test.gcc();
+    build.executor(executor.clone()).file("foo.c");
+    build.compile("foo");
+
+    // A plain compile only probes the compiler's family, falling back to
+    // `--version` as the shim doesn't answer the detection, and its target.
+    let probes: Vec<String> = executor
+        .kinds
+        .lock()
+        .unwrap()
+        .iter()
+        .zip(executor.args.lock().unwrap().iter())
+        .filter(|(kind, _)| **kind == cc::CommandKind::Probe)
+        .map(|(_, args)| args.last().unwrap().clone())
+        .collect();
+    assert!(
+        probes.iter().all(|arg| arg == "-dumpmachine"
+            || arg == "--version"
+            || arg.ends_with("detect_compiler_family.c")),
+        "{:?}",
+        probes
+    );
+    let dumpmachine = probes.iter().filter(|arg| *arg == "-dumpmachine");
+    assert_eq!(dumpmachine.count(), 1, "{:?}", probes);
+
+    let ran = |arg: &str| {
+        executor
+            .args
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|args| args.iter().any(|a| a == arg))
+    };
+
+    // The queries of tools from the `Build` run through the executor too.
+    executor.args.lock().unwrap().clear();
+    let compiler = build.get_compiler();
+    let _ = compiler.predefined_macros();
+    let _ = compiler.system_include_dirs();
+    assert!(ran("-dM"));
+    assert!(ran("-v"));
+}
+
+#[test]
+fn gnu_plan() {
+    let test = Test::gnu();
+    let plan = test.gcc().file("foo.c").file("bar.c").plan("foo");
+
+    let args = |cmd: &std::process::Command| -> Vec<String> {
+        cmd.get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    };
+    let compile = plan.compile_commands();
+    assert_eq!(compile.len(), 2);
+    assert!(args(&compile[0]).iter().any(|arg| arg.ends_with("foo.c")));
+    assert!(args(&compile[1]).iter().any(|arg| arg.ends_with("bar.c")));
+    let archive = plan.archive_commands();
+    assert_eq!(archive.len(), 2);
+    assert_eq!(args(&archive[0])[0], "cq");
+    assert_eq!(args(&archive[1])[0], "s");
+    assert!(plan
+        .metadata()
+        .contains(&"cargo:rustc-link-lib=static=foo".to_string()));
+    assert_eq!(plan.commands().count(), 4);
+
+    // Nothing was run.
+    assert!(!test.td.path().join("out0").exists());
+}
+
+#[test]
+fn gnu_write_ninja() {
+    let test = Test::gnu();
+    let path = test.td.path().join("build.ninja");
+    test.gcc()
+        .file("foo.c")
+        .file("bar.c")
+        .write_ninja(&path, "foo");
+    assert!(!test.td.path().join("out0").exists());
+
+    let ninja = fs::read_to_string(&path).unwrap();
+    let edges: Vec<&str> = ninja
+        .lines()
+        .filter(|line| line.starts_with("build "))
+        .collect();
+    assert_eq!(edges.len(), 3, "{}", ninja);
+    assert!(edges[0].ends_with(": cc foo.c"), "{}", edges[0]);
+    assert!(edges[1].ends_with(": cc bar.c"), "{}", edges[1]);
+    assert!(edges[2].contains("libfoo.a: ar "), "{}", edges[2]);
+    assert!(ninja.contains("deps = gcc"), "{}", ninja);
+    assert!(ninja.contains(" -MD -MF "), "{}", ninja);
+    assert!(ninja.contains(" ZERO_AR_DATE=1 ar cq "), "{}", ninja);
+    assert!(ninja.contains(" ar s "), "{}", ninja);
+}
+
+#[test]
+fn gnu_dry_run_env() {
+    let mut test = Test::gnu();
+    test.env.set("CC_DRY_RUN", "1");
+    let sink = Arc::new(RecordingSink::default());
+    test.gcc()
+        .output_sink(sink.clone())
+        .file("foo.c")
+        .compile("foo");
+    assert!(!test.td.path().join("out0").exists());
+    let metadata = sink.metadata.lock().unwrap();
+    assert!(
+        metadata.contains(&"cargo:rustc-link-lib=static=foo".to_string()),
+        "{:?}",
+        metadata
+    );
+}